@@ -15,21 +15,49 @@
 // License along with this program; if not, see <http://www.gnu.org/licenses/>.
 extern crate gstreamer as gst;
 
+use crate::gst::glib::translate::from_glib;
+use crate::gst::glib::translate::IntoGlib;
+use crate::gst::prelude::BufferPoolExt;
 use crate::gst::prelude::Cast;
+use crate::gst::prelude::ChildProxyExt;
+use crate::gst::prelude::ClockExt;
+use crate::gst::prelude::ClockExtManual;
+use crate::gst::prelude::ElementClassExt;
 use crate::gst::prelude::ElementExt;
 use crate::gst::prelude::ElementExtManual;
+use crate::gst::prelude::GstBinExt;
+use crate::gst::prelude::GstBinExtManual;
 use crate::gst::prelude::GstObjectExt;
+use crate::gst::prelude::GstObjectExtManual;
 use crate::gst::prelude::GstValueExt;
 use crate::gst::prelude::ObjectExt;
 use crate::gst::prelude::PadExt;
+use crate::gst::prelude::PadExtManual;
 use crate::gst::prelude::PluginFeatureExt;
 use crate::gst::prelude::PluginFeatureExtManual;
+use crate::gst::prelude::PresetExt;
 use crate::gst::prelude::StaticType;
+use crate::gst::prelude::TocSetterExt;
 use crate::gst::prelude::URIHandlerExt;
 use ansi_term::Color;
+use clap::builder::PossibleValuesParser;
 use clap::Arg;
+use clap::ArgAction;
+use clap::ArgMatches;
 use clap::Command;
-use core::ops::ControlFlow;
+use clap_complete::Shell;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::OnceLock;
 
 const BRBLUE: Color = Color::RGB(97, 127, 166);
 const PLUGIN_NAME_COLOR: Color = BRBLUE;
@@ -45,375 +73,7019 @@ const CAPS_FEATURE_COLOR: Color = Color::Green;
 const FIELD_VALUE_COLOR: Color = BRBLUE;
 const FIELD_NAME_COLOR: Color = Color::Cyan;
 const PROP_ATTR_VALUE_COLOR: Color = Color::Cyan;
+const SRC_PAD_COLOR: Color = Color::Green;
+const SINK_PAD_COLOR: Color = Color::Red;
+const ADDED_COLOR: Color = Color::Green;
+const REMOVED_COLOR: Color = Color::Red;
 
-fn print_element_list() {
-    let registry = gst::Registry::get();
-    let mut plugins = registry.plugins();
+#[derive(Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+enum Section {
+    Factory,
+    Plugin,
+    Hierarchy,
+    Caps,
+    Pads,
+    Properties,
+    Signals,
+}
 
-    plugins.sort_by(|p1, p2| p1.plugin_name().as_str().cmp(p2.plugin_name().as_str()));
-    for plugin in &plugins {
-        let mut features = registry.features_by_plugin(&plugin.plugin_name());
+#[derive(Default)]
+struct InspectOptions {
+    sections: HashSet<Section>,
+    test_events: bool,
+    show_type_ids: bool,
+    verbose: bool,
+    annotate_fractions: bool,
+    show_tracer_hooks: bool,
+    show_meta_types: bool,
+    show_owner: bool,
+    show_latency: bool,
+    show_control_bindings: bool,
+}
 
-        features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
-        for feature in &features {
-            if let Some(element_factory) = feature.downcast_ref::<gst::ElementFactory>() {
-                println!(
-                    "{}:  {}: {}",
-                    PLUGIN_NAME_COLOR.paint(plugin.plugin_name().to_string()),
-                    ELEMENT_NAME_COLOR.paint(element_factory.name().to_string()),
-                    element_factory.longname()
-                );
-            }
-        }
+impl InspectOptions {
+    // No --section given means every section is printed.
+    fn shows_all(&self) -> bool {
+        self.sections.is_empty()
     }
+
+    fn shows(&self, section: Section) -> bool {
+        self.shows_all() || self.sections.contains(&section)
+    }
+}
+
+#[derive(Default)]
+struct ElementCache {
+    elements: HashMap<String, Option<gst::Element>>,
 }
 
-fn get_rank_name(rank: gst::Rank) -> (&'static str, u32) {
-    match rank {
-        gst::Rank::None => ("none", 0),
-        gst::Rank::Marginal => ("marginal", 64),
-        gst::Rank::Secondary => ("secondary", 128),
-        gst::Rank::Primary => ("primary", 256),
-        _ => todo!(),
+impl ElementCache {
+    fn get(&mut self, factory: &gst::ElementFactory) -> Option<&gst::Element> {
+        self.elements
+            .entry(factory.name().to_string())
+            .or_insert_with(|| factory.create_with_name(None).ok())
+            .as_ref()
     }
 }
 
-fn print_property(name: &str, value: &str, width: usize, indent: usize, colon: bool) {
-    let formatted_name = PROP_NAME_COLOR.paint(format!("{:<width$}", name));
-    let indent_str = " ".repeat(indent);
-    let colon_str = if colon { ": " } else { "" };
-    println!("{}{}{}{}", indent_str, formatted_name, colon_str, value);
+#[derive(Clone, Copy)]
+enum EscapeState {
+    Text,
+    Escape,
+    Csi,
+    Osc,
+    OscEscape,
 }
 
-fn print_property_details(name: &str, value: &str) {
-    print_property(name, value, 25, 2, false);
+// Removes ANSI color codes and OSC hyperlinks from everything written
+// through it, for terminals and files that cannot render them.
+struct StripEscapes<W: Write> {
+    inner: W,
+    state: EscapeState,
 }
 
-fn print_factory_details_info(factory: &gst::ElementFactory) {
-    // FIXME: gst::PluginFeature::rank() should return int32, instead of Rank.
-    let (rank_name, rank) = get_rank_name(factory.rank());
-    println!("{}", HEADING_COLOR.paint("Factory details:"));
-    print_property_details("Rank", &format!("{} ({})", rank_name, rank));
-    print_property_details("Long name", factory.longname());
-    print_property_details("Klass", factory.klass());
-    print_property_details("Description", factory.description());
-    print_property_details("Author", factory.author());
-    println!();
-}
-
-fn print_plugin_info(plugin: &gst::Plugin) {
-    println!("{}", HEADING_COLOR.paint("Plugin details:"));
-    print_property_details("Name", plugin.plugin_name().as_str());
-    print_property_details("Description", plugin.description().as_str());
-    print_property_details(
-        "Filename",
-        &plugin.filename().map_or("(null)".to_string(), |f| {
-            f.into_os_string().into_string().unwrap()
-        }),
-    ); // FIXME: unwrap?
-    print_property_details("Version", plugin.version().as_str());
-    print_property_details("License", plugin.license().as_str());
-    print_property_details("Source module", plugin.source().as_str());
-    if let Some(release_date) = plugin.release_date_string() {
-        // TODO: Hnandle YYYY-MM-DD, YYYY-MM-DDTHH:MHZ, YYYY-MM-DDTHH:MMZ or YYYY-MM-DD HH:MM (UTC)
-        print_property_details("Source release date", release_date.as_str());
+impl<W: Write> StripEscapes<W> {
+    fn new(inner: W) -> Self {
+        StripEscapes {
+            inner,
+            state: EscapeState::Text,
+        }
     }
-    print_property_details("Binary package", plugin.package().as_str());
-    print_property_details("Origin URL", plugin.origin().as_str());
-    println!();
 }
 
-fn hierarchy_foreach<F>(type_: gst::glib::Type, foreach_func: &mut F)
-where
-    F: FnMut(gst::glib::Type),
-{
-    if let Some(parent) = type_.parent() {
-        hierarchy_foreach(parent, foreach_func);
+impl<W: Write> Write for StripEscapes<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut text = Vec::with_capacity(buf.len());
+        for &byte in buf {
+            self.state = match (self.state, byte) {
+                (EscapeState::Text, 0x1b) => EscapeState::Escape,
+                (EscapeState::Text, _) => {
+                    text.push(byte);
+                    EscapeState::Text
+                }
+                (EscapeState::Escape, b'[') => EscapeState::Csi,
+                (EscapeState::Escape, b']') => EscapeState::Osc,
+                (EscapeState::Escape, _) => EscapeState::Text,
+                (EscapeState::Csi, 0x40..=0x7e) => EscapeState::Text,
+                (EscapeState::Csi, _) => EscapeState::Csi,
+                (EscapeState::Osc, 0x07) => EscapeState::Text,
+                (EscapeState::Osc, 0x1b) => EscapeState::OscEscape,
+                (EscapeState::Osc, _) => EscapeState::Osc,
+                (EscapeState::OscEscape, b'\\') => EscapeState::Text,
+                (EscapeState::OscEscape, _) => EscapeState::Osc,
+            };
+        }
+        self.inner.write_all(&text)?;
+        Ok(buf.len())
     }
 
-    foreach_func(type_);
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
 }
 
-fn print_hierarchy(type_: gst::glib::Type) {
-    let mut level = 0;
-    let mut func = |cur_type: gst::glib::Type| {
-        if level > 0 {
-            print!("{}", "     ".repeat(level - 1));
-            print!(" {}", CHILD_LINK_COLOR.paint("+----"));
-        }
-        println!("{}", DATA_TYPE_COLOR.paint(cur_type.name()));
-        level += 1;
-    };
+fn stdout_supports_color() -> bool {
+    if std::env::var("TERM").map_or(false, |term| term == "dumb") {
+        return false;
+    }
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    #[cfg(windows)]
+    if ansi_term::enable_ansi_support().is_err() {
+        return false;
+    }
 
-    hierarchy_foreach(type_, &mut func);
-    println!();
+    true
 }
 
-fn print_interfaces(type_: gst::glib::Type) {
-    let interfaces = type_.interfaces();
-    if interfaces.is_empty() {
-        return;
+fn rank_from_str(s: &str) -> Result<u32, String> {
+    if let Some((_, value)) = RANKS.iter().find(|(name, _)| *name == s) {
+        return Ok(*value as u32);
     }
+    s.parse::<u32>().map_err(|_| {
+        let names: Vec<&str> = RANKS.iter().map(|(name, _)| *name).collect();
+        format!(
+            "'{}' is not a rank, expected {} or a number",
+            s,
+            names.join(", ")
+        )
+    })
+}
 
-    println!("{}:", HEADING_COLOR.paint("Implemented Interfaces"));
-    for iface in interfaces.as_slice() {
-        println!("  {}", DATA_TYPE_COLOR.paint(iface.name()));
-    }
-    println!();
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum SortKey {
+    Name,
+    Rank,
+    Plugin,
+    Klass,
 }
 
-fn print_caps(caps: &gst::Caps) {
-    let indent = " ".repeat(6);
+fn feature_klass(feature: &gst::PluginFeature) -> &str {
+    feature
+        .downcast_ref::<gst::ElementFactory>()
+        .map_or("", |factory| factory.klass())
+}
 
-    if caps.is_any() {
-        println!("{}{}", indent, CAPS_TYPE_COLOR.paint("ANY"));
-        return;
-    }
-    if caps.is_empty() {
-        println!("{}{}", indent, CAPS_TYPE_COLOR.paint("EMPTY"));
-        return;
+fn sort_features(key: SortKey, features: &mut [(String, gst::PluginFeature)]) {
+    features.sort_by(|(plugin1, f1), (plugin2, f2)| {
+        let by_name = || f1.name().as_str().cmp(f2.name().as_str());
+        match key {
+            SortKey::Name => by_name(),
+            SortKey::Rank => f2
+                .rank()
+                .into_glib()
+                .cmp(&f1.rank().into_glib())
+                .then_with(by_name),
+            SortKey::Plugin => plugin1.cmp(plugin2).then_with(by_name),
+            SortKey::Klass => feature_klass(f1).cmp(feature_klass(f2)).then_with(by_name),
+        }
+    });
+}
+
+fn print_element_list(
+    w: &mut dyn Write,
+    compact: bool,
+    min_rank: u32,
+    sort_key: SortKey,
+    all_features: bool,
+) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut features = Vec::new();
+
+    for plugin in registry.plugins() {
+        for feature in registry.features_by_plugin(&plugin.plugin_name()) {
+            if feature.rank().into_glib() < min_rank as i32 {
+                continue;
+            }
+            if feature.is::<gst::ElementFactory>() || feature.is::<gst::TracerFactory>() {
+                features.push((plugin.plugin_name().to_string(), feature));
+            }
+        }
     }
+    sort_features(sort_key, &mut features);
 
-    for i in 0..caps.size() {
-        if let Some(structure) = caps.structure(i) {
-            match caps.features(i) {
-                Some(f) if f.is_any() || !f.is_equal(&gst::CAPS_FEATURES_MEMORY_SYSTEM_MEMORY) => {
-                    println!(
-                        "{}{}({})",
-                        indent,
-                        STRUCT_NAME_COLOR.paint(structure.name().as_str()),
-                        CAPS_FEATURE_COLOR.paint(f.to_string()),
-                    );
-                }
-                _ => println!(
-                    "{}{}",
-                    indent,
-                    STRUCT_NAME_COLOR.paint(structure.name().as_str())
-                ),
+    for (plugin_name, feature) in &features {
+        if compact {
+            let description = match feature.downcast_ref::<gst::ElementFactory>() {
+                Some(element_factory) => element_factory.description(),
+                None => "tracer",
             };
-            structure.foreach(|q, v| {
-                if let Ok(val) = v.serialize() {
-                    let width = 23;
-                    println!(
-                        "{}: {}",
-                        FIELD_NAME_COLOR.paint(format!("{:>width$}", q.as_str().to_string())),
-                        FIELD_VALUE_COLOR.paint(val.as_str())
-                    );
-                }
-                ControlFlow::Continue(())
-            });
+            writeln!(w, "{}/{} - {}", plugin_name, feature.name(), description)?;
+        } else if let Some(element_factory) = feature.downcast_ref::<gst::ElementFactory>() {
+            writeln!(
+                w,
+                "{}:  {}: {}",
+                PLUGIN_NAME_COLOR.paint(plugin_name),
+                ELEMENT_NAME_COLOR.paint(element_factory.name().to_string()),
+                element_factory.longname()
+            )?;
+        } else {
+            writeln!(
+                w,
+                "{}:  {}: [tracer]",
+                PLUGIN_NAME_COLOR.paint(plugin_name),
+                ELEMENT_NAME_COLOR.paint(feature.name().to_string()),
+            )?;
         }
     }
+    // The summary would get in the way of scripts parsing --compact.
+    if !compact {
+        print_feature_count_summary(w, all_features)?;
+    }
+
+    Ok(())
 }
 
-fn print_pad_templates_info(factory: &gst::ElementFactory) {
-    let n_pads = factory.num_pad_templates();
-    let indent = 2;
+// Tracer factories have no description of their own, the plugin one stands in.
+fn print_tracer_list(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut tracers: Vec<gst::PluginFeature> = registry.features(gst::TracerFactory::static_type());
+    tracers.sort_by(|t1, t2| t1.name().as_str().cmp(t2.name().as_str()));
 
-    println!("{}:", HEADING_COLOR.paint("Pad Templates"));
-    if n_pads == 0 {
-        println!(" none");
-        return;
+    for tracer in &tracers {
+        let plugin = tracer.plugin();
+        writeln!(
+            w,
+            "{}:  {}: {}",
+            PLUGIN_NAME_COLOR.paint(plugin.as_ref().map_or("(null)".to_string(), |plugin| {
+                plugin.plugin_name().to_string()
+            })),
+            ELEMENT_NAME_COLOR.paint(tracer.name().to_string()),
+            plugin
+                .as_ref()
+                .map_or(String::new(), |plugin| plugin.description().to_string())
+        )?;
     }
 
-    let mut pad_templates = factory.static_pad_templates().clone();
-    pad_templates.sort_by(|t1, t2| t1.name_template().cmp(t2.name_template()));
+    Ok(())
+}
 
-    for pad_tmpl in pad_templates {
-        let availability = match pad_tmpl.presence() {
-            gst::PadPresence::Always => "Always",
-            gst::PadPresence::Sometimes => "Sometimes",
-            gst::PadPresence::Request => "On request",
-            // FIXME?: gst::PadPresence::Unknown => "UNKNOWN",
-        };
+fn print_device_provider_list(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut providers: Vec<gst::DeviceProviderFactory> = registry
+        .features(gst::DeviceProviderFactory::static_type())
+        .into_iter()
+        .filter_map(|feature| feature.downcast::<gst::DeviceProviderFactory>().ok())
+        .collect();
+    providers.sort_by(|p1, p2| p1.name().as_str().cmp(p2.name().as_str()));
 
-        print_property(
-            &format!(
-                "{} template",
-                match pad_tmpl.direction() {
-                    gst::PadDirection::Src => "SOURCE",
-                    gst::PadDirection::Sink => "SINK",
-                    gst::PadDirection::Unknown => "UNKNOWN",
-                }
-            ),
-            &format!("'{}'", pad_tmpl.name_template()),
-            0,
-            indent,
-            true,
-        );
-        print_property("Availability", availability, 0, indent * 2, true);
-        print_property("Capabilities", "", 0, indent * 2, true);
-        print_caps(&pad_tmpl.caps());
-        println!();
+    for provider in &providers {
+        let plugin_name = provider
+            .plugin_name()
+            .map_or("(null)".to_string(), |name| name.to_string());
+        writeln!(
+            w,
+            "{}:  {}: {}",
+            PLUGIN_NAME_COLOR.paint(plugin_name),
+            ELEMENT_NAME_COLOR.paint(provider.name().to_string()),
+            provider.description()
+        )?;
     }
+
+    Ok(())
 }
 
-fn print_clocking_info(element: &gst::Element) {
-    let flags = element.element_flags();
-    let requires_clock = flags.intersects(gst::ElementFlags::REQUIRE_CLOCK);
-    let provides_clock = flags.intersects(gst::ElementFlags::PROVIDE_CLOCK);
+fn count_features(registry: &gst::Registry) -> (usize, usize) {
+    let n_elements = registry.features(gst::ElementFactory::static_type()).len();
 
-    if requires_clock || provides_clock {
-        let indent = " ".repeat(2);
+    (n_elements, registry.plugins().len())
+}
 
-        println!();
-        print_property("Clocking interaction", "", 0, 0, true);
+fn feature_type_label(type_: gst::glib::Type) -> String {
+    match type_.name() {
+        "GstElementFactory" => "elements".to_string(),
+        "GstTypeFindFactory" => "typefinders".to_string(),
+        "GstDeviceProviderFactory" => "device providers".to_string(),
+        "GstTracerFactory" => "tracers".to_string(),
+        "GstDynamicTypeFactory" => "dynamic types".to_string(),
+        name => name.to_string(),
+    }
+}
 
-        print!("{}", indent);
-        if requires_clock {
-            println!("{}", "element requires a clock");
-        }
-        if provides_clock {
-            if let Some(clock) = element.clock() {
-                println!(
-                    "{}: {}",
-                    PROP_VALUE_COLOR.paint("element provides a clock"),
-                    DATA_TYPE_COLOR.paint(clock.name().as_str())
-                );
-            } else {
-                println!(
-                    "{}",
-                    PROP_VALUE_COLOR
-                        .paint("element is supposed to provide a clock but returned NULL")
-                );
+fn print_feature_count_summary(w: &mut dyn Write, all_features: bool) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let (n_elements, n_plugins) = count_features(&registry);
+
+    writeln!(w)?;
+    writeln!(w, "Total: {} elements in {} plugins", n_elements, n_plugins)?;
+    if all_features {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for plugin in registry.plugins() {
+            for feature in registry.features_by_plugin(&plugin.plugin_name()) {
+                *counts
+                    .entry(feature_type_label(feature.type_()))
+                    .or_default() += 1;
             }
         }
-    } else {
-        println!("Element has no clocking capabilities.");
+        let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+        counts.sort_by(|(l1, c1), (l2, c2)| c2.cmp(c1).then(l1.cmp(l2)));
+        for (label, count) in counts {
+            print_property(w, &label, &count.to_string(), 16, 2, true)?;
+        }
     }
+
+    Ok(())
 }
 
-fn print_uri_handler_info(element: &gst::Element) {
-    if let Some(uri_handler) = element.dynamic_cast_ref::<gst::URIHandler>() {
-        let indent = " ".repeat(2);
-        let uri_type = match uri_handler.uri_type() {
-            gst::URIType::Src => "source",
-            gst::URIType::Sink => "sink",
-            gst::URIType::Unknown => "unknown",
+fn print_element_list_by_klass(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut groups: HashMap<String, Vec<(String, String, String)>> = HashMap::new();
+
+    for feature in registry.features(gst::ElementFactory::static_type()) {
+        let factory = match feature.downcast::<gst::ElementFactory>() {
+            Ok(factory) => factory,
+            Err(_) => continue,
         };
-        println!();
-        println!("{}", HEADING_COLOR.paint("URI handling capabilities:"));
-        println!("{}Element can act as {}.", indent, uri_type);
+        let plugin_name = factory
+            .plugin_name()
+            .map_or("(null)".to_string(), |name| name.to_string());
+        groups
+            .entry(factory.klass().to_string())
+            .or_default()
+            .push((
+                plugin_name,
+                factory.name().to_string(),
+                factory.longname().to_string(),
+            ));
+    }
 
-        let uri_protocols = uri_handler.protocols();
-        if uri_protocols.is_empty() {
-            println!(
-                "{}{}",
-                indent,
-                PROP_VALUE_COLOR.paint("No supported URI protocols")
-            );
-        } else {
-            println!("{}Supported URI protocols:", indent);
+    let mut klasses: Vec<&String> = groups.keys().collect();
+    klasses.sort();
+    for (i, klass) in klasses.iter().enumerate() {
+        if i > 0 {
+            writeln!(w)?;
         }
-        uri_protocols.iter().for_each(|prot| {
-            let indent = indent.repeat(2);
-            println!("{}{}", indent, PROP_ATTR_VALUE_COLOR.paint(prot.as_str()));
+        writeln!(w, "{}", HEADING_COLOR.paint(format!("=== {} ===", klass)))?;
+
+        let mut entries = groups[*klass].clone();
+        entries.sort_by(|(plugin1, name1, _), (plugin2, name2, _)| {
+            plugin1.cmp(plugin2).then_with(|| name1.cmp(name2))
         });
-    } else {
-        println!("Element has no URI handling capabilities.");
+        for (plugin_name, element_name, longname) in &entries {
+            writeln!(
+                w,
+                "{}:  {}: {}",
+                PLUGIN_NAME_COLOR.paint(plugin_name),
+                ELEMENT_NAME_COLOR.paint(element_name),
+                longname
+            )?;
+        }
     }
-}
 
-fn print_pad_info(element: &gst::Element) {
-    let indent = 2;
+    Ok(())
+}
 
-    println!();
-    println!("{}", HEADING_COLOR.paint("Pads:"));
+fn print_elements_without_pads_list(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut factories: Vec<gst::ElementFactory> = registry
+        .features(gst::ElementFactory::static_type())
+        .into_iter()
+        .filter_map(|feature| feature.downcast::<gst::ElementFactory>().ok())
+        .filter(|factory| factory.num_pad_templates() == 0)
+        .collect();
 
-    if element.num_pads() == 0 {
-        println!("{}{}", &" ".repeat(indent), "none");
+    factories.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+    for factory in &factories {
+        writeln!(
+            w,
+            "{}:  {} [{}]: {}",
+            PLUGIN_NAME_COLOR.paint(
+                factory
+                    .plugin_name()
+                    .map_or("(null)".to_string(), |name| name.to_string())
+            ),
+            ELEMENT_NAME_COLOR.paint(factory.name().to_string()),
+            DATA_TYPE_COLOR.paint(factory.klass()),
+            factory.description()
+        )?;
     }
 
-    for pad in &element.pads() {
-        print_property(
-            match pad.direction() {
-                gst::PadDirection::Src => "SRC",
-                gst::PadDirection::Sink => "SINK",
-                gst::PadDirection::Unknown => "UNKNOWN",
-            },
-            &format!("'{}'", pad.name().as_str()),
-            0,
-            indent,
-            true,
-        );
+    Ok(())
+}
 
-        if let Some(pad_tmpl) = pad.pad_template() {
-            print_property(
-                "Pad Template",
-                &format!("'{}'", pad_tmpl.name_template()),
-                0,
-                indent * 2,
-                true,
-            );
-        }
+fn print_elements_with_preset_list(w: &mut dyn Write, cache: &mut ElementCache) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut entries = Vec::new();
 
-        if let Some(caps) = pad.current_caps() {
-            print_property("Capabilities", "", 0, indent * 2, true);
-            print_caps(&caps);
+    for feature in registry.features(gst::ElementFactory::static_type()) {
+        let factory = match feature.downcast::<gst::ElementFactory>() {
+            Ok(factory) => factory,
+            Err(_) => continue,
+        };
+        let n_presets = match cache
+            .get(&factory)
+            .and_then(|element| element.dynamic_cast_ref::<gst::Preset>())
+        {
+            Some(preset) => preset.preset_names().len(),
+            None => continue,
+        };
+        if n_presets > 0 {
+            let plugin_name = factory
+                .plugin_name()
+                .map_or("(null)".to_string(), |name| name.to_string());
+            entries.push((plugin_name, factory.name().to_string(), n_presets));
         }
     }
-}
 
-fn print_element_info(feature: &gst::PluginFeature) -> i32 {
-    let factory = feature.load();
-    if factory.is_err() {
-        println!("selement plugin couldn't be loaded");
-        return -1;
+    entries.sort_by(|(_, name1, n1), (_, name2, n2)| n2.cmp(n1).then_with(|| name1.cmp(name2)));
+    for (plugin_name, element_name, n_presets) in &entries {
+        writeln!(
+            w,
+            "{}:  {}: {} presets",
+            PLUGIN_NAME_COLOR.paint(plugin_name),
+            ELEMENT_NAME_COLOR.paint(element_name),
+            n_presets
+        )?;
     }
 
-    let element_factory = factory
-        .as_ref()
-        .unwrap()
-        .downcast_ref::<gst::ElementFactory>();
-    assert!(!element_factory.is_none());
+    Ok(())
+}
 
-    let element = element_factory.unwrap().create_with_name(None);
-    if element.is_err() {
-        println!("couldn't construct element for some reason");
-        return -1;
+fn print_elements_with_property_list(
+    w: &mut dyn Write,
+    cache: &mut ElementCache,
+    property_name: &str,
+) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut factories: Vec<gst::ElementFactory> = registry
+        .features(gst::ElementFactory::static_type())
+        .into_iter()
+        .filter_map(|feature| feature.downcast::<gst::ElementFactory>().ok())
+        .collect();
+    factories.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+
+    for factory in &factories {
+        let pspec = match cache
+            .get(factory)
+            .and_then(|element| element.find_property(property_name))
+        {
+            Some(pspec) => pspec,
+            None => continue,
+        };
+        let plugin_name = factory
+            .plugin_name()
+            .map_or("(null)".to_string(), |name| name.to_string());
+        let default = pspec
+            .default_value()
+            .serialize()
+            .map_or("?".to_string(), |default| default.to_string());
+        writeln!(
+            w,
+            "{} ({}): {}, default={}",
+            ELEMENT_NAME_COLOR.paint(factory.name().to_string()),
+            PLUGIN_NAME_COLOR.paint(plugin_name),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name()),
+            PROP_VALUE_COLOR.paint(default)
+        )?;
     }
 
-    print_factory_details_info(element_factory.unwrap());
-    if let Some(plugin) = feature.plugin() {
-        print_plugin_info(&plugin);
+    Ok(())
+}
+
+// GStreamer release in which some commonly used elements first appeared. The
+// registry only knows the version of the installed plugins, so elements that
+// are not listed here are assumed to be available in any version.
+const ELEMENT_SINCE_VERSIONS: [(&str, (u32, u32)); 24] = [
+    ("splitmuxsink", (1, 6)),
+    ("splitmuxsrc", (1, 6)),
+    ("webrtcdsp", (1, 10)),
+    ("audiobuffersplit", (1, 12)),
+    ("webrtcbin", (1, 14)),
+    ("hlssink2", (1, 14)),
+    ("av1dec", (1, 14)),
+    ("av1enc", (1, 14)),
+    ("fakevideosink", (1, 14)),
+    ("srtsrc", (1, 16)),
+    ("srtsink", (1, 16)),
+    ("dav1ddec", (1, 16)),
+    ("clocksync", (1, 18)),
+    ("dashsink", (1, 18)),
+    ("rtpsrc", (1, 18)),
+    ("rtpsink", (1, 18)),
+    ("d3d11videosink", (1, 18)),
+    ("v4l2slh264dec", (1, 18)),
+    ("fakeaudiosink", (1, 20)),
+    ("vah264dec", (1, 20)),
+    ("vah265dec", (1, 20)),
+    ("qroverlay", (1, 20)),
+    ("h264timestamper", (1, 22)),
+    ("svtav1enc", (1, 22)),
+];
+
+fn parse_gst_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |minor| minor.parse().ok())?;
+    Some((major, minor))
+}
+
+fn print_element_list_by_version(w: &mut dyn Write, version: &str) -> io::Result<i32> {
+    let target = match parse_gst_version(version) {
+        Some(target) => target,
+        None => {
+            writeln!(
+                w,
+                "Invalid GStreamer version '{}', expected MAJOR.MINOR",
+                version
+            )?;
+            return Ok(-1);
+        }
+    };
+
+    let registry = gst::Registry::get();
+    let mut plugins = registry.plugins();
+
+    plugins.sort_by(|p1, p2| p1.plugin_name().as_str().cmp(p2.plugin_name().as_str()));
+    for plugin in &plugins {
+        let mut features = registry.features_by_plugin(&plugin.plugin_name());
+
+        features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+        for feature in &features {
+            let element_factory = match feature.downcast_ref::<gst::ElementFactory>() {
+                Some(element_factory) => element_factory,
+                None => continue,
+            };
+            let since = ELEMENT_SINCE_VERSIONS
+                .iter()
+                .find(|(name, _)| *name == element_factory.name().as_str())
+                .map(|(_, since)| *since);
+            if matches!(since, Some(since) if since > target) {
+                continue;
+            }
+            writeln!(
+                w,
+                "{}:  {}: {}",
+                PLUGIN_NAME_COLOR.paint(plugin.plugin_name().to_string()),
+                ELEMENT_NAME_COLOR.paint(element_factory.name().to_string()),
+                element_factory.longname()
+            )?;
+        }
     }
-    let gtype = element.as_ref().unwrap().type_();
-    print_hierarchy(gtype);
-    print_interfaces(gtype);
-    print_pad_templates_info(element_factory.unwrap());
-    print_clocking_info(&element.as_ref().unwrap());
-    print_uri_handler_info(&element.as_ref().unwrap());
-    print_pad_info(&element.as_ref().unwrap());
 
-    return 0;
+    Ok(0)
 }
 
-fn print_feature_info(feature_name: &str) -> i32 {
+// Demuxers and parsers are the elements that extract tags from a stream and
+// send them downstream. Confirming that an element really emits tags would
+// need a media file to feed it, so only an explicit "tags" signal is noted.
+fn print_elements_providing_tags_list(w: &mut dyn Write) -> io::Result<()> {
     let registry = gst::Registry::get();
+    let mut elements = Vec::new();
 
-    let feature = registry.find_feature(feature_name, gst::ElementFactory::static_type());
-    if feature.is_none() {
-        println!("No such element or plugin '{}'", feature_name);
-        return -1;
+    for feature in registry.features(gst::ElementFactory::static_type()) {
+        let factory = match feature.downcast::<gst::ElementFactory>() {
+            Ok(factory) => factory,
+            Err(_) => continue,
+        };
+        let klass = factory.klass();
+        if !klass.contains("Demuxer") && !klass.contains("Parser") {
+            continue;
+        }
+
+        let media_type = factory
+            .static_pad_templates()
+            .iter()
+            .filter(|pad_tmpl| pad_tmpl.direction() == gst::PadDirection::Sink)
+            .find_map(|pad_tmpl| {
+                let caps = pad_tmpl.caps();
+                caps.structure(0).map(|s| s.name().to_string())
+            })
+            .unwrap_or_else(|| "ANY".to_string());
+        let tags_signal = factory.load().map_or(false, |factory| {
+            gst::glib::subclass::signal::SignalId::lookup("tags", factory.element_type()).is_some()
+        });
+        elements.push((media_type, factory, tags_signal));
     }
+    elements.sort_by(|(m1, f1, _), (m2, f2, _)| {
+        m1.cmp(m2).then(f1.name().as_str().cmp(f2.name().as_str()))
+    });
 
-    print_element_info(&feature.unwrap());
+    for (media_type, factory, tags_signal) in &elements {
+        writeln!(
+            w,
+            "{}:  {}: {}{}",
+            DATA_TYPE_COLOR.paint(media_type),
+            ELEMENT_NAME_COLOR.paint(factory.name().to_string()),
+            factory.longname(),
+            if *tags_signal { " [tags signal]" } else { "" }
+        )?;
+    }
 
-    return 0;
+    Ok(())
 }
 
-fn main() {
-    let matches = Command::new("prog")
-        .arg(Arg::new("ELEMENT-NAME | PLUGIN-NAME"))
-        .get_matches();
-    let mut st: i32 = 0;
+fn print_element_list_by_filename(w: &mut dyn Write, pattern: &str) -> io::Result<i32> {
+    let pattern = match glob::Pattern::new(pattern) {
+        Ok(pattern) => pattern,
+        Err(err) => {
+            writeln!(w, "Invalid pattern '{}': {}", pattern, err)?;
+            return Ok(-1);
+        }
+    };
 
-    gst::init().unwrap();
-    if let Some(fname) = matches.get_one::<String>("ELEMENT-NAME | PLUGIN-NAME") {
-        st = print_feature_info(fname);
-    } else {
-        print_element_list();
+    let registry = gst::Registry::get();
+    let mut plugins = registry.plugins();
+
+    plugins.sort_by(|p1, p2| p1.plugin_name().as_str().cmp(p2.plugin_name().as_str()));
+    for plugin in &plugins {
+        let matched = plugin
+            .filename()
+            .as_ref()
+            .and_then(|filename| filename.to_str())
+            .map_or(false, |filename| pattern.matches(filename));
+        if !matched {
+            continue;
+        }
+
+        let mut features = registry.features_by_plugin(&plugin.plugin_name());
+        features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+        for feature in &features {
+            if let Some(element_factory) = feature.downcast_ref::<gst::ElementFactory>() {
+                writeln!(
+                    w,
+                    "{}:  {}: {}",
+                    PLUGIN_NAME_COLOR.paint(plugin.plugin_name().to_string()),
+                    ELEMENT_NAME_COLOR.paint(element_factory.name().to_string()),
+                    element_factory.longname()
+                )?;
+            }
+        }
     }
 
-    std::process::exit(st);
+    Ok(0)
+}
+
+const RANKS: [(&str, i32); 4] = [
+    ("none", 0),
+    ("marginal", 64),
+    ("secondary", 128),
+    ("primary", 256),
+];
+
+// Ranks in between the named ones are shown relative to the nearest one,
+// like gst-inspect-1.0 does, e.g. "primary + 10".
+fn get_rank_name(rank: gst::Rank) -> (String, i32) {
+    let rank = rank.into_glib();
+    let (name, value) = RANKS.iter().fold(RANKS[0], |best, &(name, value)| {
+        if (rank - value).abs() < (rank - best.1).abs() {
+            (name, value)
+        } else {
+            best
+        }
+    });
+    let rank_name = match rank - value {
+        0 => name.to_string(),
+        diff if diff > 0 => format!("{} + {}", name, diff),
+        diff => format!("{} - {}", name, -diff),
+    };
+    (rank_name, rank)
+}
+
+fn direction_color(dir: gst::PadDirection) -> Color {
+    match dir {
+        gst::PadDirection::Src => SRC_PAD_COLOR,
+        gst::PadDirection::Sink => SINK_PAD_COLOR,
+        gst::PadDirection::Unknown => PROP_NAME_COLOR,
+    }
+}
+
+fn print_property(
+    w: &mut dyn Write,
+    name: &str,
+    value: &str,
+    width: usize,
+    indent: usize,
+    colon: bool,
+) -> io::Result<()> {
+    print_colored_property(w, name, PROP_NAME_COLOR, value, width, indent, colon)
+}
+
+fn print_colored_property(
+    w: &mut dyn Write,
+    name: &str,
+    color: Color,
+    value: &str,
+    width: usize,
+    indent: usize,
+    colon: bool,
+) -> io::Result<()> {
+    let formatted_name = color.paint(format!("{:<width$}", name));
+    let indent_str = " ".repeat(indent);
+    let colon_str = if colon { ": " } else { "" };
+    writeln!(w, "{}{}{}{}", indent_str, formatted_name, colon_str, value)
+}
+
+fn print_property_details(w: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+    print_property(w, name, value, 25, 2, false)
+}
+
+// Wraps `text` in an OSC 8 escape sequence so that terminals supporting it
+// render a clickable link. Other terminals ignore the sequence.
+fn hyperlink(uri: &str, text: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", uri, text)
+}
+
+// Renders the structural pieces of the element details. The terminal
+// formatters keep the dedicated inspection layout and only provide its
+// headings, the others render the element as a document of sections.
+trait Formatter: Sync {
+    fn uses_terminal_layout(&self) -> bool {
+        false
+    }
+    fn document_start(&self, w: &mut dyn Write, name: &str) -> io::Result<()>;
+    fn document_end(&self, _w: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+    fn heading(&self, w: &mut dyn Write, title: &str) -> io::Result<()>;
+    fn section_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        self.heading(w, title)
+    }
+    fn section_end(&self, w: &mut dyn Write) -> io::Result<()>;
+    fn subsection_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()>;
+    fn subsection_end(&self, _w: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+    fn property(&self, w: &mut dyn Write, name: &str, value: &str) -> io::Result<()>;
+    fn link(&self, uri: &str) -> String;
+    fn tree_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()>;
+    fn tree_item(
+        &self,
+        w: &mut dyn Write,
+        level: usize,
+        name: &str,
+        suffix: &str,
+    ) -> io::Result<()>;
+    fn caps(&self, w: &mut dyn Write, caps: &gst::Caps) -> io::Result<()>;
+    fn table(&self, w: &mut dyn Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()>;
+    // A section rendered in the terminal layout, for the sections that have
+    // no structured representation.
+    fn preformatted(&self, w: &mut dyn Write, title: &str, text: &str) -> io::Result<()> {
+        self.heading(w, title)?;
+        writeln!(w, "{}", text)?;
+        self.section_end(w)
+    }
+    // Written after each element when several are printed at once.
+    fn document_separator(&self, _w: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct TerminalFormatter;
+
+impl Formatter for TerminalFormatter {
+    fn uses_terminal_layout(&self) -> bool {
+        true
+    }
+
+    fn document_separator(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w)?;
+        writeln!(w, "{}", "-".repeat(80))?;
+        writeln!(w)
+    }
+
+    fn document_start(&self, _w: &mut dyn Write, _name: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn heading(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "{}", HEADING_COLOR.paint(format!("{}:", title)))
+    }
+
+    fn section_end(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w)
+    }
+
+    fn subsection_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "  {}", PROP_NAME_COLOR.paint(title))
+    }
+
+    fn property(&self, w: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+        print_property_details(w, name, value)
+    }
+
+    fn link(&self, uri: &str) -> String {
+        hyperlink(uri, uri)
+    }
+
+    fn tree_start(&self, _w: &mut dyn Write, _title: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn tree_item(
+        &self,
+        w: &mut dyn Write,
+        level: usize,
+        name: &str,
+        suffix: &str,
+    ) -> io::Result<()> {
+        if level > 0 {
+            write!(w, "{}", "     ".repeat(level - 1))?;
+            write!(w, " {}", CHILD_LINK_COLOR.paint("+----"))?;
+        }
+        writeln!(w, "{}{}", DATA_TYPE_COLOR.paint(name), suffix)
+    }
+
+    fn caps(&self, w: &mut dyn Write, caps: &gst::Caps) -> io::Result<()> {
+        print_caps(w, caps, false)
+    }
+
+    fn table(&self, w: &mut dyn Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+        let widths: Vec<usize> = (0..header.len())
+            .map(|i| {
+                rows.iter()
+                    .map(|row| row[i].len())
+                    .chain([header[i].len()])
+                    .max()
+                    .unwrap()
+            })
+            .collect();
+        let header: Vec<String> = header
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect();
+        writeln!(w, "    {}", HEADING_COLOR.paint(header.join("  ")))?;
+        for row in rows {
+            let row: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+                .collect();
+            writeln!(w, "    {}", row.join("  "))?;
+        }
+
+        Ok(())
+    }
+}
+
+// The terminal layout without colors or hyperlinks.
+struct PlainFormatter;
+
+impl Formatter for PlainFormatter {
+    fn uses_terminal_layout(&self) -> bool {
+        true
+    }
+
+    fn document_separator(&self, w: &mut dyn Write) -> io::Result<()> {
+        TerminalFormatter.document_separator(w)
+    }
+
+    fn document_start(&self, _w: &mut dyn Write, _name: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn heading(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "{}:", title)
+    }
+
+    fn section_end(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w)
+    }
+
+    fn subsection_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "  {}", title)
+    }
+
+    fn property(&self, w: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+        TerminalFormatter.property(&mut StripEscapes::new(w), name, value)
+    }
+
+    fn link(&self, uri: &str) -> String {
+        uri.to_string()
+    }
+
+    fn tree_start(&self, _w: &mut dyn Write, _title: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn tree_item(
+        &self,
+        w: &mut dyn Write,
+        level: usize,
+        name: &str,
+        suffix: &str,
+    ) -> io::Result<()> {
+        TerminalFormatter.tree_item(&mut StripEscapes::new(w), level, name, suffix)
+    }
+
+    fn caps(&self, w: &mut dyn Write, caps: &gst::Caps) -> io::Result<()> {
+        TerminalFormatter.caps(&mut StripEscapes::new(w), caps)
+    }
+
+    fn table(&self, w: &mut dyn Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+        TerminalFormatter.table(&mut StripEscapes::new(w), header, rows)
+    }
+}
+
+struct MarkdownFormatter;
+
+fn markdown_escape_cell(cell: &str) -> String {
+    cell.replace('|', "\\|")
+}
+
+impl Formatter for MarkdownFormatter {
+    fn document_start(&self, w: &mut dyn Write, name: &str) -> io::Result<()> {
+        writeln!(w, "# {}", name)?;
+        writeln!(w)
+    }
+
+    fn heading(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "## {}", title)?;
+        writeln!(w)
+    }
+
+    fn section_end(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w)
+    }
+
+    fn subsection_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w)?;
+        writeln!(w, "### {}", title)?;
+        writeln!(w)
+    }
+
+    fn property(&self, w: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+        writeln!(w, "- **{}**: {}", name, value)
+    }
+
+    fn link(&self, uri: &str) -> String {
+        format!("<{}>", uri)
+    }
+
+    fn preformatted(&self, w: &mut dyn Write, title: &str, text: &str) -> io::Result<()> {
+        self.heading(w, title)?;
+        writeln!(w, "```")?;
+        writeln!(w, "{}", text)?;
+        writeln!(w, "```")?;
+        writeln!(w)
+    }
+
+    fn document_separator(&self, w: &mut dyn Write) -> io::Result<()> {
+        writeln!(w, "---")?;
+        writeln!(w)
+    }
+
+    fn tree_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        self.section_start(w, title)
+    }
+
+    fn tree_item(
+        &self,
+        w: &mut dyn Write,
+        level: usize,
+        name: &str,
+        suffix: &str,
+    ) -> io::Result<()> {
+        writeln!(w, "{}- `{}`{}", "  ".repeat(level), name, suffix)
+    }
+
+    fn caps(&self, w: &mut dyn Write, caps: &gst::Caps) -> io::Result<()> {
+        writeln!(w)?;
+        writeln!(w, "```")?;
+        if caps.is_any() || caps.is_empty() {
+            writeln!(w, "{}", caps)?;
+        }
+        for structure in caps.iter() {
+            writeln!(w, "{}", structure)?;
+        }
+        writeln!(w, "```")
+    }
+
+    fn table(&self, w: &mut dyn Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+        writeln!(w)?;
+        writeln!(w, "| {} |", header.join(" | "))?;
+        writeln!(w, "|{}", "---|".repeat(header.len()))?;
+        for row in rows {
+            let row: Vec<String> = row.iter().map(|cell| markdown_escape_cell(cell)).collect();
+            writeln!(w, "| {} |", row.join(" | "))?;
+        }
+        writeln!(w)
+    }
+}
+
+// Writes one JSON object per line so that every call stays self-contained,
+// which keeps the formatter stateless and usable from the --all worker
+// threads. Consumers rebuild the nesting from the section records.
+struct JsonFormatter;
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+impl Formatter for JsonFormatter {
+    fn document_start(&self, w: &mut dyn Write, name: &str) -> io::Result<()> {
+        writeln!(w, "{{\"element\": {}}}", json_string(name))
+    }
+
+    fn heading(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "{{\"section\": {}}}", json_string(title))
+    }
+
+    fn section_end(&self, _w: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn subsection_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        writeln!(w, "{{\"subsection\": {}}}", json_string(title))
+    }
+
+    fn property(&self, w: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+        writeln!(
+            w,
+            "{{\"property\": {}, \"value\": {}}}",
+            json_string(name),
+            json_string(value)
+        )
+    }
+
+    fn link(&self, uri: &str) -> String {
+        uri.to_string()
+    }
+
+    fn tree_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        self.section_start(w, title)
+    }
+
+    fn tree_item(
+        &self,
+        w: &mut dyn Write,
+        level: usize,
+        name: &str,
+        suffix: &str,
+    ) -> io::Result<()> {
+        writeln!(
+            w,
+            "{{\"level\": {}, \"item\": {}}}",
+            level,
+            json_string(&format!("{}{}", name, suffix))
+        )
+    }
+
+    fn caps(&self, w: &mut dyn Write, caps: &gst::Caps) -> io::Result<()> {
+        writeln!(w, "{{\"caps\": {}}}", json_string(&caps.to_string()))
+    }
+
+    fn preformatted(&self, w: &mut dyn Write, title: &str, text: &str) -> io::Result<()> {
+        writeln!(
+            w,
+            "{{\"section\": {}, \"text\": {}}}",
+            json_string(title),
+            json_string(text)
+        )
+    }
+
+    fn table(&self, w: &mut dyn Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+        let rows: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<String> = header
+                    .iter()
+                    .zip(row)
+                    .map(|(name, cell)| format!("{}: {}", json_string(name), json_string(cell)))
+                    .collect();
+                format!("{{{}}}", cells.join(", "))
+            })
+            .collect();
+        writeln!(w, "{{\"table\": [{}]}}", rows.join(", "))
+    }
+}
+
+// The plain terminal layout without headings, used to render a section body
+// that is then handed to another formatter as preformatted text.
+struct SectionBodyFormatter;
+
+impl Formatter for SectionBodyFormatter {
+    fn uses_terminal_layout(&self) -> bool {
+        true
+    }
+
+    fn document_start(&self, _w: &mut dyn Write, _name: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn heading(&self, _w: &mut dyn Write, _title: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn section_end(&self, _w: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn subsection_start(&self, w: &mut dyn Write, title: &str) -> io::Result<()> {
+        PlainFormatter.subsection_start(w, title)
+    }
+
+    fn property(&self, w: &mut dyn Write, name: &str, value: &str) -> io::Result<()> {
+        PlainFormatter.property(w, name, value)
+    }
+
+    fn link(&self, uri: &str) -> String {
+        PlainFormatter.link(uri)
+    }
+
+    fn tree_start(&self, _w: &mut dyn Write, _title: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn tree_item(
+        &self,
+        w: &mut dyn Write,
+        level: usize,
+        name: &str,
+        suffix: &str,
+    ) -> io::Result<()> {
+        PlainFormatter.tree_item(w, level, name, suffix)
+    }
+
+    fn caps(&self, w: &mut dyn Write, caps: &gst::Caps) -> io::Result<()> {
+        PlainFormatter.caps(w, caps)
+    }
+
+    fn table(&self, w: &mut dyn Write, header: &[&str], rows: &[Vec<String>]) -> io::Result<()> {
+        PlainFormatter.table(w, header, rows)
+    }
+}
+
+fn print_factory_details_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    factory: &gst::ElementFactory,
+) -> io::Result<()> {
+    // FIXME: gst::PluginFeature::rank() should return int32, instead of Rank.
+    let (rank_name, rank) = get_rank_name(factory.rank());
+    fmt.section_start(w, "Factory details")?;
+    fmt.property(w, "Rank", &format!("{} ({})", rank_name, rank))?;
+    fmt.property(w, "Long name", factory.longname())?;
+    fmt.property(w, "Klass", factory.klass())?;
+    fmt.property(w, "Description", factory.description())?;
+    fmt.property(w, "Author", factory.author())?;
+    if let Some(doc_uri) = factory.documentation_uri() {
+        fmt.property(w, "Documentation", &fmt.link(doc_uri))?;
+    }
+    if let Some(icon_name) = factory.icon_name() {
+        fmt.property(w, "Icon name", icon_name)?;
+    }
+    fmt.section_end(w)?;
+
+    Ok(())
+}
+
+// Formats a timestamp as "YYYY-MM-DD HH:MM" in UTC.
+fn format_utc_timestamp(time: std::time::SystemTime) -> Option<String> {
+    let secs = time.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs() as i64;
+    let (days, secs_of_day) = (secs.div_euclid(86400), secs.rem_euclid(86400));
+
+    // Civil date from days since the epoch, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    Some(format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60
+    ))
+}
+
+fn parse_hh_mm(s: &str) -> Option<(u32, u32)> {
+    let (hours, minutes) = s.split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?);
+    (hours < 24 && minutes < 60).then_some((hours, minutes))
+}
+
+// Plugins give their release date as YYYY-MM-DD, YYYY-MM-DDTHH:MMZ or
+// YYYY-MM-DD HH:MM (UTC). Any of them is printed as the date followed by the
+// year, e.g. "2023-10-01 (2023)", the time only has to be valid. A time
+// without the Z or (UTC) marker is rejected since its time zone is unknown.
+fn parse_release_date(s: &str) -> Option<String> {
+    let (date, time) = (s.get(..10)?, &s[10..]);
+    let mut parts = date.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day) = (
+        year.parse::<u32>().ok()?,
+        month.parse::<u32>().ok()?,
+        day.parse::<u32>().ok()?,
+    );
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // The time of day is validated but not shown, the date is what matters.
+    if !time.is_empty() {
+        let time = time
+            .strip_prefix('T')
+            .and_then(|t| t.strip_suffix('Z'))
+            .or_else(|| {
+                time.strip_prefix(' ')
+                    .and_then(|t| t.strip_suffix(" (UTC)"))
+            })?;
+        parse_hh_mm(time)?;
+    }
+
+    Some(format!("{:04}-{:02}-{:02} ({})", year, month, day, year))
+}
+
+// Tells how the dynamic linker resolves the libraries of an ELF plugin.
+// Binaries in other formats yield None.
+// Plugin details are printed once per element, so the result is kept per
+// plugin file to avoid reading the whole library again for every feature.
+fn plugin_library_resolution(path: &std::path::Path) -> Option<String> {
+    static CACHE: OnceLock<Mutex<HashMap<std::path::PathBuf, Option<String>>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    if let Some(resolution) = cache.lock().unwrap().get(path) {
+        return resolution.clone();
+    }
+
+    let resolution = read_library_resolution(path);
+    cache
+        .lock()
+        .unwrap()
+        .insert(path.to_path_buf(), resolution.clone());
+    resolution
+}
+
+fn read_library_resolution(path: &std::path::Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let elf = goblin::elf::Elf::parse(&bytes).ok()?;
+
+    Some(if !elf.rpaths.is_empty() {
+        format!("RPATH ({})", elf.rpaths.join(":"))
+    } else if !elf.runpaths.is_empty() {
+        format!("RUNPATH ({})", elf.runpaths.join(":"))
+    } else {
+        "system LD_LIBRARY_PATH".to_string()
+    })
+}
+
+fn print_plugin_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    plugin: &gst::Plugin,
+) -> io::Result<()> {
+    fmt.section_start(w, "Plugin details")?;
+    fmt.property(w, "Name", plugin.plugin_name().as_str())?;
+    fmt.property(w, "Description", plugin.description().as_str())?;
+    fmt.property(
+        w,
+        "Filename",
+        &plugin.filename().map_or("(null)".to_string(), |f| {
+            f.into_os_string().into_string().unwrap()
+        }),
+    )?; // FIXME: unwrap?
+    let installed = plugin
+        .filename()
+        .and_then(|f| std::fs::metadata(f).ok())
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(format_utc_timestamp);
+    if let Some(installed) = installed {
+        fmt.property(w, "Installed", &installed)?;
+    }
+    let resolution = plugin
+        .filename()
+        .and_then(|f| plugin_library_resolution(&f));
+    if let Some(resolution) = resolution {
+        fmt.property(w, "Library resolution", &resolution)?;
+    }
+    fmt.property(w, "Version", plugin.version().as_str())?;
+    fmt.property(w, "License", plugin.license().as_str())?;
+    fmt.property(w, "Source module", plugin.source().as_str())?;
+    if let Some(release_date) = plugin.release_date_string() {
+        let release_date =
+            parse_release_date(release_date.as_str()).unwrap_or_else(|| release_date.to_string());
+        fmt.property(w, "Source release date", &release_date)?;
+    }
+    fmt.property(w, "Binary package", plugin.package().as_str())?;
+    fmt.property(w, "Origin URL", plugin.origin().as_str())?;
+    if let Some(cache_data) = plugin.cache_data() {
+        fmt.property(w, "Cache data", &cache_data.to_string())?;
+    }
+    fmt.section_end(w)?;
+
+    Ok(())
+}
+
+fn hierarchy_foreach<F>(type_: gst::glib::Type, foreach_func: &mut F) -> io::Result<()>
+where
+    F: FnMut(gst::glib::Type) -> io::Result<()>,
+{
+    if let Some(parent) = type_.parent() {
+        hierarchy_foreach(parent, foreach_func)?;
+    }
+
+    foreach_func(type_)
+}
+
+fn print_hierarchy(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    type_: gst::glib::Type,
+    show_type_ids: bool,
+) -> io::Result<()> {
+    let mut level = 0;
+    fmt.tree_start(w, "Type hierarchy")?;
+    let mut func = |cur_type: gst::glib::Type| {
+        let suffix = if show_type_ids {
+            format!(" ({})", cur_type.into_glib())
+        } else {
+            String::new()
+        };
+        fmt.tree_item(w, level, cur_type.name(), &suffix)?;
+        level += 1;
+        Ok(())
+    };
+
+    hierarchy_foreach(type_, &mut func)?;
+    fmt.section_end(w)?;
+
+    Ok(())
+}
+
+fn print_interfaces(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    type_: gst::glib::Type,
+) -> io::Result<()> {
+    let interfaces = type_.interfaces();
+    if interfaces.is_empty() {
+        return Ok(());
+    }
+
+    fmt.heading(w, "Implemented Interfaces")?;
+    for iface in interfaces.as_slice() {
+        writeln!(w, "  {}", DATA_TYPE_COLOR.paint(iface.name()))?;
+    }
+    writeln!(w)?;
+
+    Ok(())
+}
+
+fn print_caps(w: &mut dyn Write, caps: &gst::Caps, annotate_fractions: bool) -> io::Result<()> {
+    let indent = " ".repeat(6);
+
+    if caps.is_any() {
+        writeln!(w, "{}{}", indent, CAPS_TYPE_COLOR.paint("ANY"))?;
+        return Ok(());
+    }
+    if caps.is_empty() {
+        writeln!(w, "{}{}", indent, CAPS_TYPE_COLOR.paint("EMPTY"))?;
+        return Ok(());
+    }
+
+    for i in 0..caps.size() {
+        if let Some(structure) = caps.structure(i) {
+            match caps.features(i) {
+                Some(f) if f.is_any() || !f.is_equal(&gst::CAPS_FEATURES_MEMORY_SYSTEM_MEMORY) => {
+                    writeln!(
+                        w,
+                        "{}{}({})",
+                        indent,
+                        STRUCT_NAME_COLOR.paint(structure.name().as_str()),
+                        CAPS_FEATURE_COLOR.paint(f.to_string()),
+                    )?;
+                }
+                _ => writeln!(
+                    w,
+                    "{}{}",
+                    indent,
+                    STRUCT_NAME_COLOR.paint(structure.name().as_str())
+                )?,
+            };
+            print_structure_fields(w, structure, 23, annotate_fractions)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_structure_fields(
+    w: &mut dyn Write,
+    structure: &gst::StructureRef,
+    width: usize,
+    annotate_fractions: bool,
+) -> io::Result<()> {
+    for (field, value) in structure.iter() {
+        if let Some(val) = format_field_value(value, width + 2) {
+            let annotation = if annotate_fractions {
+                annotate_fraction_value(value)
+            } else {
+                None
+            };
+            writeln!(
+                w,
+                "{}: {}{}",
+                FIELD_NAME_COLOR.paint(format!("{:>width$}", field.as_str())),
+                FIELD_VALUE_COLOR.paint(val.as_str()),
+                annotation.map_or(String::new(), |annotation| format!(" ({})", annotation))
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn format_int_range<T: std::fmt::Display + PartialEq + From<u8>>(
+    min: T,
+    max: T,
+    step: T,
+) -> String {
+    if step == T::from(1) {
+        format!("[ {}, {} ]", min, max)
+    } else {
+        format!("[ {}, {} ] step {}", min, max, step)
+    }
+}
+
+// Lists longer than this are printed with one value per line.
+const MAX_INLINE_LIST_LEN: usize = 4;
+
+fn format_field_value(value: &gst::glib::SendValue, indent: usize) -> Option<String> {
+    if let Ok(range) = value.get::<gst::IntRange<i32>>() {
+        Some(format_int_range(range.min(), range.max(), range.step()))
+    } else if let Ok(range) = value.get::<gst::IntRange<i64>>() {
+        Some(format_int_range(range.min(), range.max(), range.step()))
+    } else if let Ok(list) = value.get::<gst::List>() {
+        let items: Vec<String> = list
+            .as_slice()
+            .iter()
+            .filter_map(|item| item.serialize().ok())
+            .map(|item| item.to_string())
+            .collect();
+        if items.len() > MAX_INLINE_LIST_LEN {
+            let item_indent = " ".repeat(indent + 2);
+            Some(format!(
+                "{{\n{}{}\n{}}}",
+                item_indent,
+                items.join(&format!(",\n{}", item_indent)),
+                " ".repeat(indent)
+            ))
+        } else {
+            Some(format!("{{ {} }}", items.join(", ")))
+        }
+    } else {
+        value.serialize().ok().map(|val| val.to_string())
+    }
+}
+
+fn format_fps(fraction: gst::Fraction) -> String {
+    let (numer, denom) = (fraction.numer(), fraction.denom());
+    if numer % denom == 0 {
+        (numer / denom).to_string()
+    } else {
+        format!("{:.2}", numer as f64 / denom as f64)
+    }
+}
+
+fn annotate_fraction(fraction: gst::Fraction) -> Option<String> {
+    match (fraction.numer(), fraction.denom()) {
+        (_, 0) => None,
+        (0, _) => Some("variable".to_string()),
+        (1, 1_000) => Some("1 ms per tick".to_string()),
+        (1, 1_000_000) => Some("1 us per tick".to_string()),
+        (1, 1_000_000_000) => Some("1 ns per tick".to_string()),
+        (numer, _) if numer < 0 => None,
+        _ => Some(format!("{} fps", format_fps(fraction))),
+    }
+}
+
+fn annotate_fraction_value(value: &gst::glib::SendValue) -> Option<String> {
+    if let Ok(fraction) = value.get::<gst::Fraction>() {
+        annotate_fraction(fraction)
+    } else if let Ok(range) = value.get::<gst::FractionRange>() {
+        if range.min().denom() == 0 || range.max().denom() == 0 {
+            return None;
+        }
+        Some(format!(
+            "{}..{} fps",
+            format_fps(range.min()),
+            format_fps(range.max())
+        ))
+    } else {
+        None
+    }
+}
+
+// The pad templates registered on the element class carry the GType of the
+// pads created from them, which the static templates do not.
+fn pad_template_gtype(
+    factory: &gst::ElementFactory,
+    name_template: &str,
+) -> Option<gst::glib::Type> {
+    let class = gst::glib::Class::<gst::Element>::from_type(factory.element_type())?;
+    class
+        .pad_template(name_template)
+        .map(|pad_tmpl| pad_tmpl.gtype())
+}
+
+fn caps_stream_type(caps: &gst::Caps) -> gst::StreamType {
+    let mut stream_type = gst::StreamType::empty();
+    if caps.is_any() {
+        return stream_type;
+    }
+    for structure in caps.iter() {
+        let name = structure.name();
+        if name.starts_with("audio/") {
+            stream_type |= gst::StreamType::AUDIO;
+        } else if name.starts_with("video/") || name.starts_with("image/") {
+            stream_type |= gst::StreamType::VIDEO;
+        } else if name.starts_with("text/")
+            || name.starts_with("subtitle/")
+            || name.starts_with("closedcaption/")
+            || name.starts_with("application/x-subtitle")
+        {
+            stream_type |= gst::StreamType::TEXT;
+        }
+    }
+    stream_type
+}
+
+fn stream_type_names(stream_type: gst::StreamType) -> Vec<&'static str> {
+    [
+        (gst::StreamType::AUDIO, "audio"),
+        (gst::StreamType::VIDEO, "video"),
+        (gst::StreamType::TEXT, "text"),
+        (gst::StreamType::CONTAINER, "container"),
+    ]
+    .iter()
+    .filter(|(flag, _)| stream_type.contains(*flag))
+    .map(|(_, name)| *name)
+    .collect()
+}
+
+fn print_pad_templates_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    factory: &gst::ElementFactory,
+    options: &InspectOptions,
+) -> io::Result<()> {
+    let n_pads = factory.num_pad_templates();
+    let indent = 2;
+
+    fmt.heading(w, "Pad Templates")?;
+    if n_pads == 0 {
+        writeln!(w, " none")?;
+        return Ok(());
+    }
+    print_property(
+        w,
+        "Number of pad templates",
+        &n_pads.to_string(),
+        0,
+        indent,
+        true,
+    )?;
+    writeln!(w)?;
+
+    let mut pad_templates = factory.static_pad_templates().clone();
+    pad_templates.sort_by(|t1, t2| t1.name_template().cmp(t2.name_template()));
+
+    for pad_tmpl in pad_templates {
+        let availability = match pad_tmpl.presence() {
+            gst::PadPresence::Always => "Always",
+            gst::PadPresence::Sometimes => "Sometimes",
+            gst::PadPresence::Request => "On request",
+            // FIXME?: gst::PadPresence::Unknown => "UNKNOWN",
+        };
+
+        print_colored_property(
+            w,
+            &format!(
+                "{} template",
+                match pad_tmpl.direction() {
+                    gst::PadDirection::Src => "SOURCE",
+                    gst::PadDirection::Sink => "SINK",
+                    gst::PadDirection::Unknown => "UNKNOWN",
+                }
+            ),
+            direction_color(pad_tmpl.direction()),
+            &format!("'{}'", pad_tmpl.name_template()),
+            0,
+            indent,
+            true,
+        )?;
+        print_property(w, "Availability", availability, 0, indent * 2, true)?;
+        let pad_type = pad_template_gtype(factory, pad_tmpl.name_template())
+            .filter(|pad_type| pad_type.is_valid() && *pad_type != gst::Pad::static_type());
+        if let Some(pad_type) = pad_type {
+            print_property(w, "Pad type", pad_type.name(), 0, indent * 2, true)?;
+        }
+        // Element metadata has no pad count keys, but a request template
+        // without a conversion specifier can only be requested once.
+        if pad_tmpl.presence() == gst::PadPresence::Request {
+            let max_pads = if pad_tmpl.name_template().contains('%') {
+                "unlimited"
+            } else {
+                "1"
+            };
+            print_property(w, "Maximum pads", max_pads, 0, indent * 2, true)?;
+        }
+        // Sometimes source pads are what demuxers and parsers expose for each
+        // stream they announce in a GstStreamCollection.
+        if pad_tmpl.direction() == gst::PadDirection::Src
+            && pad_tmpl.presence() == gst::PadPresence::Sometimes
+        {
+            let stream_types = stream_type_names(caps_stream_type(&pad_tmpl.caps()));
+            if !stream_types.is_empty() {
+                print_property(
+                    w,
+                    "Stream type",
+                    &stream_types.join(", "),
+                    0,
+                    indent * 2,
+                    true,
+                )?;
+            }
+        }
+        print_property(w, "Capabilities", "", 0, indent * 2, true)?;
+        print_caps(w, &pad_tmpl.caps(), options.annotate_fractions)?;
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
+fn memory_type_description(feature: &str) -> Option<&'static str> {
+    match feature {
+        "memory:SystemMemory" => Some("System RAM"),
+        "memory:DMABuf" => Some("DMA-BUF (zero-copy)"),
+        "memory:CUDAMemory" => Some("CUDA device memory"),
+        "memory:GLMemory" => Some("OpenGL texture"),
+        "memory:VAMemory" | "memory:VASurface" => Some("VA-API surface"),
+        "memory:D3D11Memory" => Some("Direct3D 11 texture"),
+        "memory:D3D12Memory" => Some("Direct3D 12 texture"),
+        "memory:NVMM" => Some("NVIDIA NVMM buffer"),
+        "memory:VulkanImage" => Some("Vulkan image"),
+        "memory:VulkanBuffer" => Some("Vulkan buffer"),
+        _ => None,
+    }
+}
+
+fn print_output_memory_hints(w: &mut dyn Write, factory: &gst::ElementFactory) -> io::Result<()> {
+    let mut hints = Vec::new();
+
+    for pad_tmpl in factory.static_pad_templates() {
+        if pad_tmpl.direction() != gst::PadDirection::Src {
+            continue;
+        }
+        let caps = pad_tmpl.caps();
+        if caps.is_any() {
+            continue;
+        }
+        for (_, features) in caps.iter_with_features() {
+            // Caps without a memory feature are backed by system memory.
+            let mut described = false;
+            for feature in features.iter() {
+                if let Some(description) = memory_type_description(feature.as_str()) {
+                    described = true;
+                    if !hints.contains(&description) {
+                        hints.push(description);
+                    }
+                }
+            }
+            if !described && !hints.contains(&"System RAM") {
+                hints.push("System RAM");
+            }
+        }
+    }
+
+    for hint in &hints {
+        print_property(w, "Output memory", hint, 0, 0, true)?;
+    }
+    if !hints.is_empty() {
+        writeln!(w)?;
+    }
+
+    Ok(())
+}
+
+// Meta types commonly attached to output buffers, keyed by the klass
+// components (all of which must be present) or name fragment of an element.
+const OUTPUT_META_TYPES: [(&[&str], &str, &str); 7] = [
+    (&["Video", "Decoder"], "", "GstVideoMeta"),
+    (&["Video", "Source"], "", "GstVideoMeta"),
+    (&["Video", "Converter"], "", "GstVideoMeta"),
+    (&[], "crop", "GstVideoCropMeta"),
+    (&["Audio", "Decoder"], "", "GstAudioMeta"),
+    (&["Overlay"], "", "GstVideoOverlayCompositionMeta"),
+    (&["Depayloader", "RTP"], "", "GstRTPSourceMeta"),
+];
+
+fn output_meta_types(factory: &gst::ElementFactory) -> Vec<&'static str> {
+    let klass: Vec<&str> = factory.klass().split('/').collect();
+    let name = factory.name();
+    let mut meta_types = Vec::new();
+
+    for (klass_components, name_fragment, meta_type) in OUTPUT_META_TYPES {
+        let matches = if name_fragment.is_empty() {
+            klass_components
+                .iter()
+                .all(|component| klass.contains(component))
+        } else {
+            name.contains(name_fragment)
+        };
+        if matches && !meta_types.contains(&meta_type) {
+            meta_types.push(meta_type);
+        }
+    }
+
+    meta_types
+}
+
+fn print_output_meta_types(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    factory: &gst::ElementFactory,
+) -> io::Result<()> {
+    let meta_types = output_meta_types(factory);
+    fmt.heading(w, "Output meta types")?;
+    if meta_types.is_empty() {
+        writeln!(w, "{}{}", &" ".repeat(2), "none")?;
+    }
+    for meta_type in meta_types {
+        writeln!(w, "  {}", DATA_TYPE_COLOR.paint(meta_type))?;
+    }
+    writeln!(w)?;
+
+    Ok(())
+}
+
+// Most system clocks have a resolution of a few nanoseconds, which a whole
+// number of microseconds would show as 0.
+fn format_clock_resolution(resolution: gst::ClockTime) -> String {
+    let ns = resolution.nseconds();
+    if ns < 1_000 {
+        format!("{} ns", ns)
+    } else {
+        format!("{:.3} us", ns as f64 / 1_000.0)
+    }
+}
+
+fn print_clock_capabilities(w: &mut dyn Write, clock: &gst::Clock) -> io::Result<()> {
+    let indent = 4;
+    let flags = clock.clock_flags();
+    let yes_no = |flag| if flags.contains(flag) { "yes" } else { "no" };
+
+    print_property(w, "Type", clock.type_().name(), 20, indent, true)?;
+    print_property(
+        w,
+        "Resolution",
+        &format_clock_resolution(clock.resolution()),
+        20,
+        indent,
+        true,
+    )?;
+
+    print_property(
+        w,
+        "Synchronous wait",
+        yes_no(gst::ClockFlags::CAN_DO_SINGLE_SYNC),
+        20,
+        indent,
+        true,
+    )?;
+    print_property(
+        w,
+        "Asynchronous wait",
+        yes_no(gst::ClockFlags::CAN_DO_SINGLE_ASYNC),
+        20,
+        indent,
+        true,
+    )?;
+    let role = match clock.master() {
+        Some(master) => format!("slave of {}", master.name()),
+        None => "master".to_string(),
+    };
+    print_property(w, "Role", &role, 20, indent, true)?;
+    if clock.type_().name() == "GstAudioClock" {
+        // An audio clock advances with the samples played by its ring
+        // buffer, whose rate is only known once caps are negotiated.
+        print_property(
+            w,
+            "Timing",
+            "driven by the negotiated sample rate",
+            20,
+            indent,
+            true,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn format_flags<F: std::fmt::Debug>(flags: F) -> Option<String> {
+    let flags = format!("{:?}", flags);
+    if flags == "(empty)" {
+        None
+    } else {
+        Some(flags)
+    }
+}
+
+fn print_gstobject_flags(w: &mut dyn Write, obj: &gst::Object, indent: usize) -> io::Result<()> {
+    let mut flags: Vec<String> = format_flags(obj.object_flags()).into_iter().collect();
+    if let Some(pad) = obj.downcast_ref::<gst::Pad>() {
+        flags.extend(format_flags(pad.pad_flags()));
+    } else if let Some(element) = obj.downcast_ref::<gst::Element>() {
+        flags.extend(format_flags(element.element_flags()));
+    }
+
+    let flags = if flags.is_empty() {
+        "none".to_string()
+    } else {
+        flags.join(" | ")
+    };
+    print_property(
+        w,
+        "Flags",
+        &PROP_ATTR_VALUE_COLOR.paint(flags).to_string(),
+        0,
+        indent,
+        true,
+    )
+}
+
+fn print_clocking_info(w: &mut dyn Write, element: &gst::Element) -> io::Result<()> {
+    let flags = element.element_flags();
+    let requires_clock = flags.intersects(gst::ElementFlags::REQUIRE_CLOCK);
+    let provides_clock = flags.intersects(gst::ElementFlags::PROVIDE_CLOCK);
+
+    if requires_clock || provides_clock {
+        let indent = " ".repeat(2);
+
+        writeln!(w)?;
+        print_property(w, "Clocking interaction", "", 0, 0, true)?;
+
+        write!(w, "{}", indent)?;
+        if requires_clock {
+            writeln!(w, "{}", "element requires a clock")?;
+        }
+        if provides_clock {
+            // A standalone element has no clock selected yet, so ask for the
+            // one it would provide instead.
+            if let Some(clock) = element.provide_clock() {
+                writeln!(
+                    w,
+                    "{}: {}",
+                    PROP_VALUE_COLOR.paint("element provides a clock"),
+                    DATA_TYPE_COLOR.paint(clock.name().as_str())
+                )?;
+                print_clock_capabilities(w, &clock)?;
+            } else {
+                writeln!(
+                    w,
+                    "{}",
+                    PROP_VALUE_COLOR
+                        .paint("element is supposed to provide a clock but returned NULL")
+                )?;
+            }
+        }
+    } else {
+        writeln!(w, "Element has no clocking capabilities.")?;
+    }
+
+    Ok(())
+}
+
+// GstIndex was dropped from the 1.0 API, so the INDEXABLE element flag is
+// all that is left to tell whether an element supports indexing.
+fn print_index_info(w: &mut dyn Write, element: &gst::Element) -> io::Result<()> {
+    if element
+        .element_flags()
+        .contains(gst::ElementFlags::INDEXABLE)
+    {
+        writeln!(w)?;
+        print_property(w, "Indexing capabilities", "", 0, 0, true)?;
+        writeln!(w, "  {}", PROP_VALUE_COLOR.paint("element can do indexing"))?;
+    } else {
+        writeln!(w, "Element has no indexing capabilities.")?;
+    }
+
+    Ok(())
+}
+
+fn print_audio_clock_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let is_audio_source = element
+        .factory()
+        .map_or(false, |factory| factory.klass().contains("Source/Audio"));
+    if !is_audio_source {
+        return Ok(());
+    }
+
+    let indent = 2;
+
+    writeln!(w)?;
+    fmt.heading(w, "Audio clock correction")?;
+
+    let is_audio_base_src = gst::glib::Type::from_name("GstAudioBaseSrc")
+        .map_or(false, |base_type| element.type_().is_a(base_type));
+    if !is_audio_base_src {
+        writeln!(
+            w,
+            "{}{}",
+            " ".repeat(indent),
+            PROP_VALUE_COLOR.paint("element is not a GstAudioBaseSrc")
+        )?;
+        return Ok(());
+    }
+
+    for (label, prop_name, unit) in [
+        ("Slave method", "slave-method", ""),
+        ("Drift tolerance", "drift-tolerance", " us"),
+    ] {
+        if element.find_property(prop_name).is_none() {
+            continue;
+        }
+        if let Ok(val) = element.property_value(prop_name).serialize() {
+            print_property(
+                w,
+                label,
+                &format!("{}{}", PROP_VALUE_COLOR.paint(val.as_str()), unit),
+                0,
+                indent,
+                true,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_uri_handler_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    if let Some(uri_handler) = element.dynamic_cast_ref::<gst::URIHandler>() {
+        let indent = " ".repeat(2);
+        let (uri_type, uri_color) = match uri_handler.uri_type() {
+            gst::URIType::Src => ("source", SRC_PAD_COLOR),
+            gst::URIType::Sink => ("sink", SINK_PAD_COLOR),
+            gst::URIType::Unknown => ("unknown", PROP_ATTR_VALUE_COLOR),
+        };
+        writeln!(w)?;
+        fmt.heading(w, "URI handling capabilities")?;
+        writeln!(
+            w,
+            "{}Element can act as {}.",
+            indent,
+            uri_color.paint(uri_type)
+        )?;
+
+        let uri_protocols = uri_handler.protocols();
+        if uri_protocols.is_empty() {
+            writeln!(
+                w,
+                "{}{}",
+                indent,
+                PROP_VALUE_COLOR.paint("No supported URI protocols")
+            )?;
+        } else {
+            writeln!(w, "{}Supported URI protocols:", indent)?;
+        }
+        for prot in uri_protocols.iter() {
+            let indent = indent.repeat(2);
+            writeln!(w, "{}{}", indent, uri_color.paint(prot.as_str()))?;
+        }
+    } else {
+        writeln!(w, "Element has no URI handling capabilities.")?;
+    }
+
+    Ok(())
+}
+
+fn print_toc_support(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let toc_setter = match element.dynamic_cast_ref::<gst::TocSetter>() {
+        Some(toc_setter) => toc_setter,
+        None => return Ok(()),
+    };
+
+    writeln!(w)?;
+    fmt.heading(w, "Table of Contents")?;
+    writeln!(
+        w,
+        "  {}",
+        PROP_VALUE_COLOR.paint("Supports TOC (Table of Contents)")
+    )?;
+    let scope = toc_setter.toc().map_or("none set".to_string(), |toc| {
+        format!("{:?}", toc.scope()).to_lowercase()
+    });
+    print_property(w, "Scope", &scope, 0, 2, true)?;
+
+    Ok(())
+}
+
+// Preset files are key files named after the element type, with one group per
+// preset.
+fn preset_file_names(dir: &std::path::Path, element: &gst::Element) -> HashSet<String> {
+    let path = dir.join(format!("{}.prs", element.type_().name()));
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return HashSet::new(),
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix('[')?.strip_suffix(']'))
+        .filter(|group| *group != "_presets_")
+        .map(|group| group.to_string())
+        .collect()
+}
+
+// The directories GstPreset reads presets from, in order of precedence: the
+// user directory where presets get saved, the application directory and
+// the system directories, including the ones listed in GST_PRESET_PATH.
+fn preset_dirs() -> Vec<(&'static str, std::path::PathBuf)> {
+    let mut dirs = vec![(
+        "[user]",
+        gst::glib::user_data_dir()
+            .join("gstreamer-1.0")
+            .join("presets"),
+    )];
+    if let Some(app_dir) = gst::Preset::app_dir() {
+        dirs.push(("[app]", app_dir));
+    }
+    if let Some(preset_path) = std::env::var_os("GST_PRESET_PATH") {
+        dirs.extend(std::env::split_paths(&preset_path).map(|dir| ("[system]", dir)));
+    }
+    dirs.extend(
+        gst::glib::system_data_dirs()
+            .into_iter()
+            .map(|dir| ("[system]", dir.join("gstreamer-1.0").join("presets"))),
+    );
+    dirs
+}
+
+fn print_preset_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let preset = match element.dynamic_cast_ref::<gst::Preset>() {
+        Some(preset) => preset,
+        None => return Ok(()),
+    };
+    let names = preset.preset_names();
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(w)?;
+    fmt.heading(w, "Presets")?;
+    if let Some(app_dir) = gst::Preset::app_dir() {
+        print_property(
+            w,
+            "Application directory",
+            &app_dir.display().to_string(),
+            0,
+            2,
+            true,
+        )?;
+    }
+    let dir_names: Vec<(&str, HashSet<String>)> = preset_dirs()
+        .iter()
+        .map(|(label, dir)| (*label, preset_file_names(dir, element)))
+        .collect();
+    for name in &names {
+        // Presets not found in any known directory are left unlabelled.
+        let origin = dir_names
+            .iter()
+            .find(|(_, dir_names)| dir_names.contains(name.as_str()))
+            .map_or(String::new(), |(label, _)| format!(" {}", label));
+        writeln!(w, "  {}{}", PROP_NAME_COLOR.paint(name.as_str()), origin)?;
+    }
+
+    Ok(())
+}
+
+fn print_toc_entry(w: &mut dyn Write, entry: &gst::TocEntry, indent: usize) -> io::Result<()> {
+    let times = entry
+        .start_stop_times()
+        .map_or(String::new(), |(start, stop)| {
+            let format_time = |time: i64| {
+                u64::try_from(time).map_or("none".to_string(), |time| {
+                    gst::ClockTime::from_nseconds(time).to_string()
+                })
+            };
+            format!(" {} - {}", format_time(start), format_time(stop))
+        });
+    let title = entry
+        .tags()
+        .and_then(|tags| {
+            tags.get::<gst::tags::Title>()
+                .map(|title| title.get().to_string())
+        })
+        .map_or(String::new(), |title| format!(" \"{}\"", title));
+    writeln!(
+        w,
+        "{}{} {}{}{}",
+        " ".repeat(indent),
+        DATA_TYPE_COLOR.paint(format!("{:?}", entry.entry_type()).to_lowercase()),
+        PROP_NAME_COLOR.paint(entry.uid()),
+        PROP_VALUE_COLOR.paint(title),
+        times
+    )?;
+    for sub_entry in entry.sub_entries() {
+        print_toc_entry(w, &sub_entry, indent + 2)?;
+    }
+
+    Ok(())
+}
+
+fn print_toc_from_file(w: &mut dyn Write, element_name: &str, path: &str) -> io::Result<i32> {
+    let element = match gst::ElementFactory::make(element_name).build() {
+        Ok(element) => element,
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    let src = match gst::ElementFactory::make("filesrc")
+        .property("location", path)
+        .build()
+    {
+        Ok(src) => src,
+        Err(_) => {
+            writeln!(w, "Could not create filesrc")?;
+            return Ok(-1);
+        }
+    };
+
+    let pipeline = gst::Pipeline::new(None);
+    if pipeline.add_many(&[&src, &element]).is_err() || src.link(&element).is_err() {
+        writeln!(w, "Could not link '{}' to a filesrc", element_name)?;
+        return Ok(-1);
+    }
+    // Demuxers expose their streams on sometimes pads, so every pad gets its
+    // own fakesink to let the element preroll.
+    let add_fakesink = |element: &gst::Element, pad: &gst::Pad| {
+        let bin = match element.parent().and_then(|p| p.downcast::<gst::Bin>().ok()) {
+            Some(bin) => bin,
+            None => return,
+        };
+        if let Ok(sink) = gst::ElementFactory::make("fakesink").build() {
+            if bin.add(&sink).is_ok() {
+                let _ = sink.sync_state_with_parent();
+                if let Some(sink_pad) = sink.static_pad("sink") {
+                    let _ = pad.link(&sink_pad);
+                }
+            }
+        }
+    };
+    for pad in element.src_pads() {
+        add_fakesink(&element, &pad);
+    }
+    element.connect_pad_added(add_fakesink);
+
+    let bus = match pipeline.bus() {
+        Some(bus) => bus,
+        None => {
+            writeln!(w, "Could not get the pipeline bus")?;
+            return Ok(-1);
+        }
+    };
+    if pipeline.set_state(gst::State::Paused).is_err() {
+        let _ = pipeline.set_state(gst::State::Null);
+        writeln!(w, "Could not play '{}' with '{}'", path, element_name)?;
+        return Ok(-1);
+    }
+    let mut toc = None;
+    while let Some(msg) = bus.timed_pop_filtered(
+        Some(5 * gst::ClockTime::SECOND),
+        &[
+            gst::MessageType::Toc,
+            gst::MessageType::AsyncDone,
+            gst::MessageType::Error,
+        ],
+    ) {
+        match msg.view() {
+            gst::MessageView::Toc(msg) => {
+                toc = Some(msg.toc().0);
+                break;
+            }
+            gst::MessageView::Error(err) => {
+                writeln!(w, "Could not read '{}': {}", path, err.error())?;
+                break;
+            }
+            _ => break,
+        }
+    }
+    let _ = pipeline.set_state(gst::State::Null);
+
+    let toc = match toc {
+        Some(toc) => toc,
+        None => {
+            writeln!(w, "No TOC found in '{}'", path)?;
+            return Ok(1);
+        }
+    };
+    print_property(
+        w,
+        "Scope",
+        &format!("{:?}", toc.scope()).to_lowercase(),
+        0,
+        0,
+        true,
+    )?;
+    for entry in toc.entries() {
+        print_toc_entry(w, &entry, 2)?;
+    }
+
+    Ok(0)
+}
+
+fn print_pad_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+    options: &InspectOptions,
+) -> io::Result<()> {
+    let indent = 2;
+
+    writeln!(w)?;
+    fmt.heading(w, "Pads")?;
+
+    if element.num_pads() == 0 {
+        writeln!(w, "{}{}", &" ".repeat(indent), "none")?;
+    }
+
+    for pad in &element.pads() {
+        // GstGhostPad derives from GstProxyPad, so check it first.
+        let pad_kind = if pad.type_().is_a(gst::GhostPad::static_type()) {
+            " [ghost]"
+        } else if pad.type_().is_a(gst::ProxyPad::static_type()) {
+            " [proxy]"
+        } else {
+            ""
+        };
+        print_colored_property(
+            w,
+            match pad.direction() {
+                gst::PadDirection::Src => "SRC",
+                gst::PadDirection::Sink => "SINK",
+                gst::PadDirection::Unknown => "UNKNOWN",
+            },
+            direction_color(pad.direction()),
+            &format!("'{}'{}", pad.name().as_str(), pad_kind),
+            0,
+            indent,
+            true,
+        )?;
+
+        print_property(w, "Path", pad.path_string().as_str(), 0, indent * 2, true)?;
+        if let Some(pad_tmpl) = pad.pad_template() {
+            print_property(
+                w,
+                "Pad Template",
+                &format!("'{}'", pad_tmpl.name_template()),
+                0,
+                indent * 2,
+                true,
+            )?;
+        }
+        print_gstobject_flags(w, pad.upcast_ref(), indent * 2)?;
+
+        if let Some(caps) = pad.current_caps() {
+            print_property(w, "Capabilities", "", 0, indent * 2, true)?;
+            print_caps(w, &caps, options.annotate_fractions)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Buffer pool options are plain strings defined by the libraries that handle
+// them, so the well-known ones are listed here with what they enable.
+const BUFFER_POOL_OPTIONS: [(&str, &str, &str); 8] = [
+    (
+        "GST_BUFFER_POOL_OPTION_VIDEO_META",
+        "GstBufferPoolOptionVideoMeta",
+        "Adds GstVideoMeta to buffers, allowing custom strides and offsets",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_VIDEO_ALIGNMENT",
+        "GstBufferPoolOptionVideoAlignment",
+        "Enables video alignment/padding for hardware decoders",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_VIDEO_AFFINE_TRANSFORMATION_META",
+        "GstBufferPoolOptionVideoAffineTransformation",
+        "Adds GstVideoAffineTransformationMeta to buffers",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_VIDEO_GL_TEXTURE_UPLOAD_META",
+        "GstBufferPoolOptionVideoGLTextureUploadMeta",
+        "Adds GstVideoGLTextureUploadMeta to buffers",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_GL_SYNC_META",
+        "GstBufferPoolOptionGLSyncMeta",
+        "Adds GstGLSyncMeta to synchronize GL contexts",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_GL_TEXTURE_TARGET_2D",
+        "GstBufferPoolOptionGLTextureTarget2D",
+        "Allocates GL memory as 2D textures",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_GL_TEXTURE_TARGET_RECTANGLE",
+        "GstBufferPoolOptionGLTextureTargetRectangle",
+        "Allocates GL memory as rectangle textures",
+    ),
+    (
+        "GST_BUFFER_POOL_OPTION_GL_TEXTURE_TARGET_EXTERNAL_OES",
+        "GstBufferPoolOptionGLTextureTargetExternalOES",
+        "Allocates GL memory as external OES textures",
+    ),
+];
+
+fn print_buffer_pool_options(
+    w: &mut dyn Write,
+    pool: &gst::BufferPool,
+    indent: usize,
+) -> io::Result<()> {
+    let options = pool.options();
+    if options.is_empty() {
+        return Ok(());
+    }
+
+    print_property(w, "Options", "", 12, indent, true)?;
+    for option in &options {
+        match BUFFER_POOL_OPTIONS
+            .iter()
+            .find(|(_, value, _)| *value == option.as_str())
+        {
+            Some((constant, value, description)) => writeln!(
+                w,
+                "{}{} ({}): {}",
+                " ".repeat(indent + 2),
+                PROP_NAME_COLOR.paint(*constant),
+                value,
+                description
+            )?,
+            None => writeln!(
+                w,
+                "{}{}",
+                " ".repeat(indent + 2),
+                PROP_NAME_COLOR.paint(option.as_str())
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+fn print_allocation_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let indent = 2;
+
+    writeln!(w)?;
+    fmt.heading(w, "Allocation")?;
+
+    // Allocation queries travel upstream, so an element answers them on its
+    // sink pads with the pools and parameters it wants buffers to use.
+    let mut answered = false;
+    for pad in element.sink_pads() {
+        let mut caps = pad.pad_template_caps();
+        if caps.is_any() || caps.is_empty() {
+            continue;
+        }
+        caps.fixate();
+
+        let mut query = gst::query::Allocation::new(Some(&caps), true);
+        if !pad.query(&mut query) {
+            continue;
+        }
+        answered = true;
+
+        print_property(w, "Pad", pad.name().as_str(), 0, indent, true)?;
+        for (pool, size, min_buffers, max_buffers) in query.allocation_pools() {
+            let pool_name = pool
+                .as_ref()
+                .map_or("(none)".to_string(), |pool| pool.type_().name().to_string());
+            print_property(w, "Pool", &pool_name, 12, indent * 2, true)?;
+            print_property(w, "Buffer size", &size.to_string(), 12, indent * 3, true)?;
+            print_property(
+                w,
+                "Buffers",
+                &format!("{} - {}", min_buffers, max_buffers),
+                12,
+                indent * 3,
+                true,
+            )?;
+            if let Some(pool) = &pool {
+                print_buffer_pool_options(w, pool, indent * 3)?;
+            }
+        }
+        for (allocator, params) in query.allocation_params() {
+            let allocator_name = allocator.map_or("(default)".to_string(), |allocator| {
+                allocator.type_().name().to_string()
+            });
+            print_property(w, "Allocator", &allocator_name, 12, indent * 2, true)?;
+            print_property(
+                w,
+                "Prefix",
+                &params.prefix().to_string(),
+                12,
+                indent * 3,
+                true,
+            )?;
+            print_property(
+                w,
+                "Padding",
+                &params.padding().to_string(),
+                12,
+                indent * 3,
+                true,
+            )?;
+            print_property(
+                w,
+                "Alignment",
+                &format!("{} bytes", params.align() + 1),
+                12,
+                indent * 3,
+                true,
+            )?;
+        }
+    }
+    if !answered {
+        writeln!(
+            w,
+            "{}{}",
+            &" ".repeat(indent),
+            PROP_VALUE_COLOR.paint("no allocation proposal")
+        )?;
+    }
+
+    Ok(())
+}
+
+fn print_scheduling_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let indent = 2;
+
+    writeln!(w)?;
+    fmt.heading(w, "Scheduling")?;
+
+    if element.num_pads() == 0 {
+        writeln!(w, "{}{}", &" ".repeat(indent), "none")?;
+    }
+
+    for pad in &element.pads() {
+        // Pads that do not answer the query use the default push scheduling.
+        let mut query = gst::query::Scheduling::new();
+        let modes = if pad.query(&mut query) {
+            let mut modes = Vec::new();
+            if query.has_scheduling_mode(gst::PadMode::Push) {
+                modes.push("Push");
+            }
+            if query.has_scheduling_mode(gst::PadMode::Pull) {
+                modes.push("Pull");
+            }
+            modes
+        } else {
+            vec!["Push"]
+        };
+
+        print_colored_property(
+            w,
+            &format!("'{}'", pad.name()),
+            direction_color(pad.direction()),
+            &modes.join(", "),
+            0,
+            indent,
+            true,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn format_fraction(fraction: gst::Fraction) -> String {
+    format!("{}/{}", fraction.numer(), fraction.denom())
+}
+
+fn format_range<T: std::fmt::Display>(type_name: &str, min: T, max: T, default: T) -> String {
+    format!(
+        "{}. Range: {} - {}. Default: {}",
+        DATA_TYPE_COLOR.paint(type_name),
+        PROP_VALUE_COLOR.paint(min.to_string()),
+        PROP_VALUE_COLOR.paint(max.to_string()),
+        PROP_VALUE_COLOR.paint(default.to_string())
+    )
+}
+
+fn print_pspec_flags(
+    w: &mut dyn Write,
+    pspec: &gst::glib::ParamSpec,
+    indent: usize,
+) -> io::Result<()> {
+    let descriptions: Vec<String> = pspec_flag_descriptions(pspec)
+        .iter()
+        .map(|description| PROP_ATTR_VALUE_COLOR.paint(*description).to_string())
+        .collect();
+    print_property(w, "flags", &descriptions.join(", "), 0, indent, true)?;
+
+    Ok(())
+}
+
+fn pspec_flag_descriptions(pspec: &gst::glib::ParamSpec) -> Vec<&'static str> {
+    let flags = pspec.flags();
+    let known_flags = [
+        (gst::glib::ParamFlags::READABLE, "readable"),
+        (gst::glib::ParamFlags::WRITABLE, "writable"),
+        (gst::glib::ParamFlags::DEPRECATED, "deprecated"),
+        (gst::glib::ParamFlags::CONSTRUCT, "construct"),
+        (gst::glib::ParamFlags::CONSTRUCT_ONLY, "construct-only"),
+        (gst::glib::ParamFlags::EXPLICIT_NOTIFY, "explicit notify"),
+        (gst::glib::ParamFlags::LAX_VALIDATION, "lax validation"),
+        (gst::PARAM_FLAG_CONTROLLABLE, "controllable"),
+        // gst::PARAM_FLAG_CONDITIONALLY_AVAILABLE is only exported with the
+        // v1_18 feature, it is the same bit.
+        (gst::glib::ParamFlags::USER_6, "conditionally available"),
+    ];
+
+    let mut descriptions: Vec<&'static str> = known_flags
+        .iter()
+        .filter(|(flag, _)| flags.contains(*flag))
+        .map(|(_, description)| *description)
+        .collect();
+    if flags.contains(gst::PARAM_FLAG_MUTABLE_PLAYING) {
+        descriptions.push("changeable in NULL, READY, PAUSED or PLAYING state");
+    } else if flags.contains(gst::PARAM_FLAG_MUTABLE_PAUSED) {
+        descriptions.push("changeable only in NULL, READY or PAUSED state");
+    } else if flags.contains(gst::PARAM_FLAG_MUTABLE_READY) {
+        descriptions.push("changeable only in NULL or READY state");
+    }
+
+    descriptions
+}
+
+// 64-bit integer properties named like this almost always hold a duration in
+// nanoseconds, as a GstClockTime would.
+const TIME_PROPERTY_PATTERNS: [&str; 5] = ["*-time", "*-duration", "timeout", "latency", "delay"];
+
+fn is_time_property(name: &str) -> bool {
+    TIME_PROPERTY_PATTERNS
+        .iter()
+        .any(|pattern| glob::Pattern::new(pattern).map_or(false, |pattern| pattern.matches(name)))
+}
+
+fn format_nanoseconds(ns: u64) -> String {
+    if ns == u64::MAX {
+        return format!("none ({} ns)", ns);
+    }
+    format!("{:.3} s ({} ns)", ns as f64 / 1_000_000_000.0, ns)
+}
+
+fn print_default_property_value(
+    w: &mut dyn Write,
+    pspec: &gst::glib::ParamSpec,
+    value: &gst::glib::Value,
+    indent: usize,
+    options: &InspectOptions,
+) -> io::Result<()> {
+    let indent_str = " ".repeat(indent);
+    let is_time = is_time_property(pspec.name());
+    let line = if pspec.downcast_ref::<gst::glib::ParamSpecString>().is_some() {
+        let default = match value.get::<Option<String>>().ok().flatten() {
+            Some(s) => format!("\"{}\"", s),
+            None => "null".to_string(),
+        };
+        format!(
+            "{}. Default: {}",
+            DATA_TYPE_COLOR.paint("String"),
+            PROP_VALUE_COLOR.paint(default)
+        )
+    } else if pspec
+        .downcast_ref::<gst::glib::ParamSpecBoolean>()
+        .is_some()
+    {
+        format!(
+            "{}. Default: {}",
+            DATA_TYPE_COLOR.paint("Boolean"),
+            PROP_VALUE_COLOR.paint(value.get::<bool>().unwrap_or(false).to_string())
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecULong>() {
+        format_range(
+            "Unsigned Long",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<gst::glib::ULong>().map_or(0, |v| v.0),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecLong>() {
+        format_range(
+            "Long",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<gst::glib::ILong>().map_or(0, |v| v.0),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecUInt>() {
+        format_range(
+            "Unsigned Integer",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<u32>().unwrap_or_default(),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecInt>() {
+        format_range(
+            "Integer",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<i32>().unwrap_or_default(),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecUInt64>() {
+        if is_time {
+            format_range(
+                "Unsigned Integer64",
+                format_nanoseconds(pspec.minimum()),
+                format_nanoseconds(pspec.maximum()),
+                format_nanoseconds(value.get::<u64>().unwrap_or_default()),
+            )
+        } else {
+            format_range(
+                "Unsigned Integer64",
+                pspec.minimum(),
+                pspec.maximum(),
+                value.get::<u64>().unwrap_or_default(),
+            )
+        }
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecInt64>() {
+        // Negative values usually mean "unset" and have no duration.
+        let format_time = |ns: i64| u64::try_from(ns).map_or(ns.to_string(), format_nanoseconds);
+        if is_time {
+            format_range(
+                "Integer64",
+                format_time(pspec.minimum()),
+                format_time(pspec.maximum()),
+                format_time(value.get::<i64>().unwrap_or_default()),
+            )
+        } else {
+            format_range(
+                "Integer64",
+                pspec.minimum(),
+                pspec.maximum(),
+                value.get::<i64>().unwrap_or_default(),
+            )
+        }
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecFloat>() {
+        format_range(
+            "Float",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<f32>().unwrap_or_default(),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecDouble>() {
+        format_range(
+            "Double",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<f64>().unwrap_or_default(),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecChar>() {
+        format_range(
+            "Char",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<i8>().unwrap_or_default(),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecUChar>() {
+        format_range(
+            "Unsigned Char",
+            pspec.minimum(),
+            pspec.maximum(),
+            value.get::<u8>().unwrap_or_default(),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::ParamSpecFraction>() {
+        format_range(
+            "Fraction",
+            format_fraction(pspec.minimum()),
+            format_fraction(pspec.maximum()),
+            format_fraction(
+                value
+                    .get::<gst::Fraction>()
+                    .unwrap_or(pspec.default_value()),
+            ),
+        )
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecEnum>() {
+        let enum_class = pspec.enum_class();
+        let default = match gst::glib::EnumValue::from_value(value) {
+            Some((_, enum_value)) => {
+                format!("({:>3}), \"{}\"", enum_value.value(), enum_value.nick())
+            }
+            None => "(invalid)".to_string(),
+        };
+        writeln!(
+            w,
+            "{}{} \"{}\" Default: {}",
+            indent_str,
+            DATA_TYPE_COLOR.paint("Enum"),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name()),
+            PROP_VALUE_COLOR.paint(default)
+        )?;
+        for enum_value in enum_class.values() {
+            writeln!(
+                w,
+                "{}   ({:>3}): {} - {}",
+                indent_str,
+                enum_value.value(),
+                PROP_VALUE_COLOR.paint(format!("{:<16}", enum_value.nick())),
+                enum_value.name()
+            )?;
+        }
+        return Ok(());
+    } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecFlags>() {
+        let flags_class = pspec.flags_class();
+        let (flags, nicks) = match gst::glib::FlagsValue::from_value(value) {
+            Some((_, flags_values)) => (
+                flags_values.iter().fold(0, |acc, v| acc | v.value()),
+                flags_values
+                    .iter()
+                    .map(|v| v.nick())
+                    .collect::<Vec<&str>>()
+                    .join("+"),
+            ),
+            None => (0, String::new()),
+        };
+        writeln!(
+            w,
+            "{}{} \"{}\" Default: {}",
+            indent_str,
+            DATA_TYPE_COLOR.paint("Flags"),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name()),
+            PROP_VALUE_COLOR.paint(format!("0x{:08x}, \"{}\"", flags, nicks))
+        )?;
+        for flags_value in flags_class.values() {
+            writeln!(
+                w,
+                "{}   (0x{:08x}): {} - {}",
+                indent_str,
+                flags_value.value(),
+                PROP_VALUE_COLOR.paint(format!("{:<16}", flags_value.nick())),
+                flags_value.name()
+            )?;
+        }
+        return Ok(());
+    } else if pspec.value_type() == gst::Caps::static_type() {
+        match value.get::<Option<gst::Caps>>().ok().flatten() {
+            Some(caps) => {
+                writeln!(w, "{}{}:", indent_str, DATA_TYPE_COLOR.paint("Caps"))?;
+                print_caps(w, &caps, options.annotate_fractions)?;
+            }
+            None => writeln!(
+                w,
+                "{}{} ({})",
+                indent_str,
+                DATA_TYPE_COLOR.paint("Caps"),
+                PROP_VALUE_COLOR.paint("NULL")
+            )?,
+        }
+        return Ok(());
+    } else if pspec.value_type().is_a(gst::glib::Type::OBJECT) {
+        let default = match value.get::<Option<gst::glib::Object>>().ok().flatten() {
+            Some(object) => match object.downcast_ref::<gst::Object>() {
+                Some(gst_object) => format!("\"{}\"", gst_object.name()),
+                None => object.type_().name().to_string(),
+            },
+            None => "(NULL)".to_string(),
+        };
+        format!(
+            "{} of type \"{}\". Default: {}",
+            DATA_TYPE_COLOR.paint("Object"),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name()),
+            PROP_VALUE_COLOR.paint(default)
+        )
+    } else if pspec.value_type() == gst::Structure::static_type() {
+        let structure = value.get::<Option<gst::Structure>>().ok().flatten();
+        writeln!(
+            w,
+            "{}{} \"{}\". Default: {}",
+            indent_str,
+            DATA_TYPE_COLOR.paint("Boxed pointer of type"),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name()),
+            PROP_VALUE_COLOR.paint(
+                structure
+                    .as_ref()
+                    .map_or("(NULL)".to_string(), |structure| structure.to_string())
+            )
+        )?;
+        if let Some(structure) = structure.filter(|_| options.verbose) {
+            print_structure_fields(w, &structure, indent + 4, options.annotate_fractions)?;
+        }
+        return Ok(());
+    } else if pspec.value_type() == gst::TagList::static_type() {
+        let type_name = format!(
+            "{} \"{}\"",
+            DATA_TYPE_COLOR.paint("Boxed pointer of type"),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name())
+        );
+        let tag_list = match value.get::<Option<gst::TagList>>().ok().flatten() {
+            Some(tag_list) => tag_list,
+            None => {
+                writeln!(
+                    w,
+                    "{}{}. Default: {}",
+                    indent_str,
+                    type_name,
+                    PROP_VALUE_COLOR.paint("(NULL tag list)")
+                )?;
+                return Ok(());
+            }
+        };
+        writeln!(w, "{}{}. Default:", indent_str, type_name)?;
+        for (tag, tag_value) in tag_list.iter() {
+            let serialized = tag_value
+                .serialize()
+                .map_or("(unserializable)".to_string(), |s| s.to_string());
+            writeln!(
+                w,
+                "{}  {} ({}): {}",
+                indent_str,
+                FIELD_NAME_COLOR.paint(tag.as_str()),
+                DATA_TYPE_COLOR.paint(tag_value.type_().name()),
+                FIELD_VALUE_COLOR.paint(serialized)
+            )?;
+        }
+        return Ok(());
+    } else if pspec.value_type().is_a(gst::glib::Type::BOXED) {
+        format!(
+            "{} \"{}\"",
+            DATA_TYPE_COLOR.paint("Boxed pointer of type"),
+            DATA_TYPE_COLOR.paint(pspec.value_type().name())
+        )
+    } else {
+        return Ok(());
+    };
+
+    writeln!(w, "{}{}", indent_str, line)?;
+
+    Ok(())
+}
+
+fn print_element_properties(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+    options: &InspectOptions,
+) -> io::Result<()> {
+    let indent = 2;
+    let name_width = 20;
+    let details_indent = indent + name_width + 2;
+
+    writeln!(w)?;
+    fmt.heading(w, "Element Properties")?;
+
+    let mut pspecs: Vec<gst::glib::ParamSpec> = element.list_properties().iter().cloned().collect();
+    if pspecs.is_empty() {
+        writeln!(w, "{}{}", &" ".repeat(indent), "none")?;
+        return Ok(());
+    }
+    pspecs.sort_by(|p1, p2| p1.name().cmp(p2.name()));
+
+    for pspec in &pspecs {
+        let value = if pspec.flags().contains(gst::glib::ParamFlags::READABLE) {
+            element.property_value(pspec.name())
+        } else {
+            pspec.default_value().clone()
+        };
+
+        writeln!(w)?;
+        print_property(
+            w,
+            pspec.name(),
+            &PROP_VALUE_COLOR
+                .paint(pspec.blurb().unwrap_or(""))
+                .to_string(),
+            name_width,
+            indent,
+            true,
+        )?;
+        if options.show_owner && pspec.owner_type() != element.type_() {
+            writeln!(
+                w,
+                "{}(inherited from {})",
+                " ".repeat(details_indent),
+                DATA_TYPE_COLOR.paint(pspec.owner_type().name())
+            )?;
+        }
+        print_pspec_flags(w, pspec, details_indent)?;
+        if pspec
+            .flags()
+            .contains(gst::glib::ParamFlags::CONSTRUCT_ONLY)
+        {
+            writeln!(
+                w,
+                "{}{}",
+                " ".repeat(details_indent),
+                PROP_ATTR_VALUE_COLOR.paint("(value readable after construction only)")
+            )?;
+        }
+        if options.show_control_bindings && pspec.flags().contains(gst::PARAM_FLAG_CONTROLLABLE) {
+            writeln!(
+                w,
+                "{}{}",
+                " ".repeat(details_indent),
+                PROP_ATTR_VALUE_COLOR.paint(format!(
+                    "(bind with gst_controller::DirectControlBinding::new(&element, \"{}\", &control_source))",
+                    pspec.name()
+                ))
+            )?;
+        }
+        print_default_property_value(w, pspec, &value, details_indent, options)?;
+    }
+
+    if options.show_control_bindings {
+        let controllable = pspecs
+            .iter()
+            .find(|pspec| pspec.flags().contains(gst::PARAM_FLAG_CONTROLLABLE));
+        if let Some(pspec) = controllable {
+            print_control_binding_example(w, fmt, element, pspec)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_control_binding_example(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+    pspec: &gst::glib::ParamSpec,
+) -> io::Result<()> {
+    let factory_name = element
+        .factory()
+        .map_or("element".to_string(), |factory| factory.name().to_string());
+
+    writeln!(w)?;
+    fmt.heading(w, "Control binding example")?;
+    for line in [
+        format!(
+            "let element = gst::ElementFactory::make(\"{}\").build()?;",
+            factory_name
+        ),
+        "let control_source = gst_controller::InterpolationControlSource::new();".to_string(),
+        "control_source.set_mode(gst_controller::InterpolationMode::Linear);".to_string(),
+        format!(
+            "let binding = gst_controller::DirectControlBinding::new(&element, \"{}\", &control_source);",
+            pspec.name()
+        ),
+        "element.add_control_binding(&binding)?;".to_string(),
+    ] {
+        writeln!(w, "  {}", line)?;
+    }
+
+    Ok(())
+}
+
+// Signals of the element type and its parents up to GstElement, whose own
+// signals are the same for every element. Action signals are meant to be
+// emitted by applications and get their own section, as in gst-inspect-1.0.
+fn print_signals_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+    actions: bool,
+) -> io::Result<()> {
+    let mut queries = Vec::new();
+    let mut type_ = Some(element.type_());
+    while let Some(t) = type_.filter(|t| *t != gst::Element::static_type()) {
+        for signal_id in signal_ids(t) {
+            let query = signal_id.query();
+            if query.flags().contains(gst::glib::SignalFlags::ACTION) == actions {
+                queries.push(query);
+            }
+        }
+        type_ = t.parent();
+    }
+    if queries.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(w)?;
+    fmt.heading(
+        w,
+        if actions {
+            "Element Actions"
+        } else {
+            "Element Signals"
+        },
+    )?;
+    for query in &queries {
+        writeln!(
+            w,
+            "  \"{}\" :  {}",
+            PROP_NAME_COLOR.paint(query.signal_name()),
+            format_signal_prototype(query.type_(), query)
+        )?;
+    }
+
+    Ok(())
+}
+
+fn print_children_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let child_proxy = match element.dynamic_cast_ref::<gst::ChildProxy>() {
+        Some(child_proxy) => child_proxy,
+        None => return Ok(()),
+    };
+    let indent = 2;
+
+    writeln!(w)?;
+    fmt.heading(w, "Children")?;
+
+    let n_children = child_proxy.children_count();
+    if n_children == 0 {
+        writeln!(w, "{}{}", &" ".repeat(indent), "none")?;
+        return Ok(());
+    }
+
+    for i in 0..n_children {
+        if let Some(child) = child_proxy.child_by_index(i) {
+            let name = child
+                .downcast_ref::<gst::Object>()
+                .map_or("(unnamed)".to_string(), |obj| obj.name().to_string());
+            print_property(
+                w,
+                &name,
+                &DATA_TYPE_COLOR.paint(child.type_().name()).to_string(),
+                0,
+                indent,
+                true,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn print_event_support(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    let indent = " ".repeat(2);
+
+    writeln!(w)?;
+    fmt.heading(w, "Event support (READY state)")?;
+
+    if element.set_state(gst::State::Ready).is_err() {
+        writeln!(
+            w,
+            "{}{}",
+            indent,
+            PROP_VALUE_COLOR.paint("element could not be set to READY state")
+        )?;
+        let _ = element.set_state(gst::State::Null);
+        return Ok(());
+    }
+
+    let events = [
+        ("FLUSH_START", gst::event::FlushStart::new()),
+        ("FLUSH_STOP", gst::event::FlushStop::new(true)),
+        (
+            "SEEK",
+            gst::event::Seek::new(
+                1.0,
+                gst::SeekFlags::FLUSH,
+                gst::SeekType::Set,
+                gst::ClockTime::ZERO,
+                gst::SeekType::None,
+                gst::ClockTime::ZERO,
+            ),
+        ),
+        ("EOS", gst::event::Eos::new()),
+        (
+            "CAPS",
+            gst::event::Caps::new(&gst::Caps::new_empty_simple("application/x-unknown")),
+        ),
+        (
+            "SEGMENT",
+            gst::event::Segment::new(&gst::FormattedSegment::<gst::ClockTime>::new()),
+        ),
+    ];
+
+    let width = 12;
+    writeln!(w, "{}{:<width$} | {}", indent, "event_type", "supported")?;
+    for (name, event) in events {
+        let supported = if element.send_event(event) {
+            "yes"
+        } else {
+            "no"
+        };
+        writeln!(
+            w,
+            "{}{} | {}",
+            indent,
+            PROP_NAME_COLOR.paint(format!("{:<width$}", name)),
+            PROP_VALUE_COLOR.paint(supported)
+        )?;
+    }
+
+    let _ = element.set_state(gst::State::Null);
+
+    Ok(())
+}
+
+fn format_latency(time: gst::ClockTime) -> String {
+    format!("{}ms", time.nseconds() as f64 / 1_000_000.0)
+}
+
+fn print_latency_query(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    writeln!(w)?;
+    fmt.heading(w, "Latency")?;
+
+    // The query is only answered once the element is linked and prerolling.
+    let pipeline = build_test_pipeline(element);
+    let mut query = gst::query::Latency::new();
+    let answered = match &pipeline {
+        Some(pipeline) => {
+            let _ = pipeline.set_state(gst::State::Paused);
+            let _ = pipeline.state(Some(gst::ClockTime::SECOND));
+            let answered = element.query(&mut query);
+            let _ = pipeline.set_state(gst::State::Null);
+            answered
+        }
+        None => false,
+    };
+
+    if !answered {
+        writeln!(
+            w,
+            "  {}",
+            PROP_VALUE_COLOR.paint("Latency query not supported")
+        )?;
+        return Ok(());
+    }
+
+    let (live, min, max) = query.result();
+    writeln!(
+        w,
+        "  Live: {}, Min latency: {}, Max latency: {}",
+        PROP_VALUE_COLOR.paint(if live { "yes" } else { "no" }),
+        PROP_VALUE_COLOR.paint(format_latency(min)),
+        PROP_VALUE_COLOR.paint(max.map_or("none".to_string(), format_latency))
+    )?;
+
+    Ok(())
+}
+
+fn print_pad_templates_doc(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    factory: &gst::ElementFactory,
+) -> io::Result<()> {
+    fmt.section_start(w, "Pad Templates")?;
+    let mut pad_templates = factory.static_pad_templates().clone();
+    pad_templates.sort_by(|t1, t2| t1.name_template().cmp(t2.name_template()));
+    if pad_templates.is_empty() {
+        writeln!(w, "none")?;
+    }
+
+    for pad_tmpl in pad_templates {
+        let direction = match pad_tmpl.direction() {
+            gst::PadDirection::Src => "SOURCE",
+            gst::PadDirection::Sink => "SINK",
+            gst::PadDirection::Unknown => "UNKNOWN",
+        };
+        let availability = match pad_tmpl.presence() {
+            gst::PadPresence::Always => "Always",
+            gst::PadPresence::Sometimes => "Sometimes",
+            gst::PadPresence::Request => "On request",
+        };
+        fmt.subsection_start(
+            w,
+            &format!("{} template '{}'", direction, pad_tmpl.name_template()),
+        )?;
+        fmt.property(w, "Availability", availability)?;
+        fmt.caps(w, &pad_tmpl.caps())?;
+        fmt.subsection_end(w)?;
+    }
+    fmt.section_end(w)?;
+
+    Ok(())
+}
+
+fn print_properties_doc(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    element: &gst::Element,
+) -> io::Result<()> {
+    fmt.section_start(w, "Element Properties")?;
+    let mut pspecs: Vec<gst::glib::ParamSpec> = element.list_properties().iter().cloned().collect();
+    pspecs.sort_by(|p1, p2| p1.name().cmp(p2.name()));
+    if pspecs.is_empty() {
+        writeln!(w, "none")?;
+    }
+
+    for pspec in &pspecs {
+        let value = if pspec.flags().contains(gst::glib::ParamFlags::READABLE) {
+            element.property_value(pspec.name())
+        } else {
+            pspec.default_value().clone()
+        };
+        let default = value
+            .serialize()
+            .map_or("(unserializable)".to_string(), |s| s.to_string());
+
+        fmt.subsection_start(w, pspec.name())?;
+        fmt.property(w, "Description", pspec.blurb().unwrap_or(""))?;
+        fmt.property(w, "Flags", &pspec_flag_descriptions(pspec).join(", "))?;
+        fmt.property(w, "Type", pspec.value_type().name())?;
+        fmt.property(w, "Default", &default)?;
+        if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecEnum>() {
+            let rows: Vec<Vec<String>> = pspec
+                .enum_class()
+                .values()
+                .iter()
+                .map(|v| {
+                    vec![
+                        v.value().to_string(),
+                        v.nick().to_string(),
+                        v.name().to_string(),
+                    ]
+                })
+                .collect();
+            fmt.table(w, &["Value", "Nick", "Name"], &rows)?;
+        } else if let Some(pspec) = pspec.downcast_ref::<gst::glib::ParamSpecFlags>() {
+            let rows: Vec<Vec<String>> = pspec
+                .flags_class()
+                .values()
+                .iter()
+                .map(|v| {
+                    vec![
+                        format!("0x{:08x}", v.value()),
+                        v.nick().to_string(),
+                        v.name().to_string(),
+                    ]
+                })
+                .collect();
+            fmt.table(w, &["Value", "Nick", "Name"], &rows)?;
+        }
+        fmt.subsection_end(w)?;
+    }
+    fmt.section_end(w)?;
+
+    Ok(())
+}
+
+// Renders a section that only has a terminal layout and passes it on to the
+// document formatter as preformatted text. Empty sections are skipped.
+fn print_section_doc<F>(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    title: &str,
+    render: F,
+) -> io::Result<()>
+where
+    F: FnOnce(&mut dyn Write, &dyn Formatter) -> io::Result<()>,
+{
+    let mut body = Vec::new();
+    render(&mut StripEscapes::new(&mut body), &SectionBodyFormatter)?;
+    let body = String::from_utf8_lossy(&body);
+    let body = body.trim_matches('\n');
+    if body.is_empty() {
+        return Ok(());
+    }
+
+    fmt.preformatted(w, title, body)
+}
+
+fn print_element_doc(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    feature: &gst::PluginFeature,
+    factory: &gst::ElementFactory,
+    element: &gst::Element,
+    options: &InspectOptions,
+) -> io::Result<()> {
+    fmt.document_start(w, factory.name().as_str())?;
+    if options.shows(Section::Factory) {
+        print_factory_details_info(w, fmt, factory)?;
+    }
+    if options.shows(Section::Plugin) {
+        if let Some(plugin) = feature.plugin() {
+            print_plugin_info(w, fmt, &plugin)?;
+        }
+    }
+    if options.shows(Section::Hierarchy) {
+        print_hierarchy(w, fmt, element.type_(), options.show_type_ids)?;
+        print_section_doc(w, fmt, "Implemented Interfaces", |w, body_fmt| {
+            print_interfaces(w, body_fmt, element.type_())
+        })?;
+    }
+    if options.shows(Section::Caps) || options.shows(Section::Pads) {
+        print_pad_templates_doc(w, fmt, factory)?;
+    }
+    if options.shows(Section::Caps) {
+        print_section_doc(w, fmt, "Output memory", |w, _| {
+            print_output_memory_hints(w, factory)
+        })?;
+        if options.show_meta_types {
+            print_section_doc(w, fmt, "Output meta types", |w, body_fmt| {
+                print_output_meta_types(w, body_fmt, factory)
+            })?;
+        }
+    }
+    if options.shows_all() {
+        print_section_doc(w, fmt, "Object flags", |w, _| {
+            print_gstobject_flags(w, element.upcast_ref(), 0)
+        })?;
+        print_section_doc(w, fmt, "Clocking", |w, _| print_clocking_info(w, element))?;
+        print_section_doc(w, fmt, "Indexing", |w, _| print_index_info(w, element))?;
+        print_section_doc(w, fmt, "Audio clock correction", |w, body_fmt| {
+            print_audio_clock_info(w, body_fmt, element)
+        })?;
+        print_section_doc(w, fmt, "URI handling capabilities", |w, body_fmt| {
+            print_uri_handler_info(w, body_fmt, element)
+        })?;
+        print_section_doc(w, fmt, "Table of Contents", |w, body_fmt| {
+            print_toc_support(w, body_fmt, element)
+        })?;
+        print_section_doc(w, fmt, "Presets", |w, body_fmt| {
+            print_preset_info(w, body_fmt, element)
+        })?;
+    }
+    if options.shows(Section::Pads) {
+        print_section_doc(w, fmt, "Pads", |w, body_fmt| {
+            print_pad_info(w, body_fmt, element, options)
+        })?;
+        print_section_doc(w, fmt, "Allocation", |w, body_fmt| {
+            print_allocation_info(w, body_fmt, element)
+        })?;
+        print_section_doc(w, fmt, "Scheduling", |w, body_fmt| {
+            print_scheduling_info(w, body_fmt, element)
+        })?;
+    }
+    if options.shows(Section::Properties) {
+        print_properties_doc(w, fmt, element)?;
+    }
+    if options.shows(Section::Signals) {
+        print_section_doc(w, fmt, "Element Signals", |w, body_fmt| {
+            print_signals_info(w, body_fmt, element, false)
+        })?;
+        print_section_doc(w, fmt, "Element Actions", |w, body_fmt| {
+            print_signals_info(w, body_fmt, element, true)
+        })?;
+    }
+    if options.shows_all() {
+        print_section_doc(w, fmt, "Children", |w, body_fmt| {
+            print_children_info(w, body_fmt, element)
+        })?;
+    }
+    if options.test_events {
+        print_section_doc(w, fmt, "Event support (READY state)", |w, body_fmt| {
+            print_event_support(w, body_fmt, element)
+        })?;
+    }
+    if options.show_latency {
+        print_section_doc(w, fmt, "Latency", |w, body_fmt| {
+            print_latency_query(w, body_fmt, element)
+        })?;
+    }
+    fmt.document_end(w)?;
+
+    Ok(())
+}
+
+fn print_element_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    feature: &gst::PluginFeature,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    let factory = feature.load();
+    if factory.is_err() {
+        writeln!(w, "selement plugin couldn't be loaded")?;
+        return Ok(-1);
+    }
+
+    let element_factory = factory
+        .as_ref()
+        .unwrap()
+        .downcast_ref::<gst::ElementFactory>();
+    assert!(!element_factory.is_none());
+
+    let element = element_factory.unwrap().create_with_name(None);
+    if element.is_err() {
+        writeln!(w, "couldn't construct element for some reason")?;
+        return Ok(-1);
+    }
+
+    if !fmt.uses_terminal_layout() {
+        print_element_doc(
+            w,
+            fmt,
+            feature,
+            element_factory.unwrap(),
+            element.as_ref().unwrap(),
+            options,
+        )?;
+        return Ok(0);
+    }
+
+    if options.shows(Section::Factory) {
+        print_factory_details_info(w, fmt, element_factory.unwrap())?;
+    }
+    if options.shows(Section::Plugin) {
+        if let Some(plugin) = feature.plugin() {
+            print_plugin_info(w, fmt, &plugin)?;
+        }
+    }
+    let gtype = element.as_ref().unwrap().type_();
+    if options.shows(Section::Hierarchy) {
+        print_hierarchy(w, fmt, gtype, options.show_type_ids)?;
+        print_interfaces(w, fmt, gtype)?;
+    }
+    if options.shows(Section::Caps) || options.shows(Section::Pads) {
+        print_pad_templates_info(w, fmt, element_factory.unwrap(), options)?;
+    }
+    if options.shows(Section::Caps) {
+        print_output_memory_hints(w, element_factory.unwrap())?;
+        if options.show_meta_types {
+            print_output_meta_types(w, fmt, element_factory.unwrap())?;
+        }
+    }
+    if options.shows_all() {
+        writeln!(w)?;
+        print_gstobject_flags(w, element.as_ref().unwrap().upcast_ref(), 0)?;
+        print_clocking_info(w, &element.as_ref().unwrap())?;
+        print_index_info(w, &element.as_ref().unwrap())?;
+        print_audio_clock_info(w, fmt, &element.as_ref().unwrap())?;
+        print_uri_handler_info(w, fmt, &element.as_ref().unwrap())?;
+        print_toc_support(w, fmt, &element.as_ref().unwrap())?;
+        print_preset_info(w, fmt, &element.as_ref().unwrap())?;
+    }
+    if options.shows(Section::Pads) {
+        print_pad_info(w, fmt, &element.as_ref().unwrap(), options)?;
+        print_allocation_info(w, fmt, &element.as_ref().unwrap())?;
+        print_scheduling_info(w, fmt, &element.as_ref().unwrap())?;
+    }
+    if options.shows(Section::Properties) {
+        print_element_properties(w, fmt, &element.as_ref().unwrap(), options)?;
+    }
+    if options.shows(Section::Signals) {
+        print_signals_info(w, fmt, &element.as_ref().unwrap(), false)?;
+        print_signals_info(w, fmt, &element.as_ref().unwrap(), true)?;
+    }
+    if options.shows_all() {
+        print_children_info(w, fmt, &element.as_ref().unwrap())?;
+    }
+    if options.test_events {
+        print_event_support(w, fmt, &element.as_ref().unwrap())?;
+    }
+    if options.show_latency {
+        print_latency_query(w, fmt, &element.as_ref().unwrap())?;
+    }
+
+    return Ok(0);
+}
+
+fn print_feature_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    feature_name: &str,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    let registry = gst::Registry::get();
+
+    let feature = registry.find_feature(feature_name, gst::ElementFactory::static_type());
+    if feature.is_none() {
+        if let Some(tracer) = registry.find_feature(feature_name, gst::TracerFactory::static_type())
+        {
+            return print_tracer_feature(w, fmt, &tracer, options);
+        }
+        writeln!(w, "No such element or plugin '{}'", feature_name)?;
+        return Ok(-1);
+    }
+
+    print_element_info(w, fmt, &feature.unwrap(), options)?;
+
+    return Ok(0);
+}
+
+fn print_tracer_feature(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    feature: &gst::PluginFeature,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    match feature.load() {
+        Ok(tracer) => print_tracer_info(
+            w,
+            fmt,
+            tracer.downcast_ref::<gst::TracerFactory>().unwrap(),
+            options,
+        ),
+        Err(_) => {
+            writeln!(w, "tracer plugin couldn't be loaded")?;
+            Ok(-1)
+        }
+    }
+}
+
+fn print_tracer_by_name(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    tracer_name: &str,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    match gst::Registry::get().find_feature(tracer_name, gst::TracerFactory::static_type()) {
+        Some(tracer) => print_tracer_feature(w, fmt, &tracer, options),
+        None => {
+            writeln!(w, "No such tracer '{}'", tracer_name)?;
+            Ok(-1)
+        }
+    }
+}
+
+fn print_tracer_info(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    factory: &gst::TracerFactory,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    let (rank_name, rank) = get_rank_name(factory.rank());
+    fmt.section_start(w, "Factory details")?;
+    fmt.property(w, "Rank", &format!("{} ({})", rank_name, rank))?;
+    fmt.property(w, "Name", factory.name().as_str())?;
+    fmt.property(w, "Type", "tracer")?;
+    fmt.section_end(w)?;
+
+    if let Some(plugin) = factory.plugin() {
+        print_plugin_info(w, fmt, &plugin)?;
+    }
+    print_hierarchy(w, fmt, factory.tracer_type(), options.show_type_ids)?;
+    if options.show_tracer_hooks {
+        print_tracer_hooks(w, fmt, factory)?;
+    }
+
+    Ok(0)
+}
+
+// Every hook a tracer can register through gst_tracing_register_hook().
+const TRACER_HOOKS: [&str; 28] = [
+    "bin-add-pre",
+    "bin-add-post",
+    "bin-remove-pre",
+    "bin-remove-post",
+    "element-new",
+    "element-add-pad",
+    "element-remove-pad",
+    "element-change-state-pre",
+    "element-change-state-post",
+    "element-post-message-pre",
+    "element-post-message-post",
+    "element-query-pre",
+    "element-query-post",
+    "pad-link-pre",
+    "pad-link-post",
+    "pad-unlink-pre",
+    "pad-unlink-post",
+    "pad-push-pre",
+    "pad-push-post",
+    "pad-push-list-pre",
+    "pad-push-list-post",
+    "pad-pull-range-pre",
+    "pad-pull-range-post",
+    "pad-push-event-pre",
+    "pad-push-event-post",
+    "pad-query-pre",
+    "pad-query-post",
+    "object-created",
+];
+
+// GStreamer keeps the registered hooks private, so the hooks of the tracers
+// shipped with GStreamer core are listed here.
+fn tracer_hooks(tracer_name: &str) -> Option<&'static [&'static str]> {
+    match tracer_name {
+        "log" => Some(&TRACER_HOOKS),
+        "leaks" => Some(&["object-created"]),
+        "latency" => Some(&[
+            "pad-push-pre",
+            "pad-push-list-pre",
+            "pad-pull-range-pre",
+            "pad-pull-range-post",
+            "pad-push-event-pre",
+            "pad-query-post",
+            "element-new",
+        ]),
+        "stats" => Some(&[
+            "pad-push-pre",
+            "pad-push-post",
+            "pad-push-list-pre",
+            "pad-push-list-post",
+            "pad-pull-range-pre",
+            "pad-pull-range-post",
+            "pad-push-event-pre",
+            "pad-query-pre",
+            "pad-query-post",
+            "element-new",
+            "element-post-message-pre",
+            "element-query-pre",
+        ]),
+        "rusage" => Some(&["pad-push-pre", "pad-push-list-pre", "pad-pull-range-pre"]),
+        _ => None,
+    }
+}
+
+fn print_tracer_hooks(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    factory: &gst::TracerFactory,
+) -> io::Result<()> {
+    let indent = " ".repeat(2);
+
+    fmt.heading(w, "Tracer hooks")?;
+    match tracer_hooks(factory.name().as_str()) {
+        Some(hooks) => {
+            for hook in hooks {
+                writeln!(w, "{}{}", indent, PROP_VALUE_COLOR.paint(*hook))?;
+            }
+        }
+        None => writeln!(w, "{}{}", indent, "unknown")?,
+    }
+    writeln!(w)?;
+
+    Ok(())
+}
+
+fn print_element_api_version(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let registry = gst::Registry::get();
+
+    let feature = match registry.find_feature(element_name, gst::ElementFactory::static_type()) {
+        Some(feature) => feature,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    let plugin = match feature.plugin() {
+        Some(plugin) => plugin,
+        None => {
+            writeln!(w, "Element '{}' does not belong to a plugin", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let (major, _, _, _) = gst::version();
+    let runtime_api = format!("{}.0", major);
+
+    // The plugin descriptor and the API version it was built for are not
+    // exposed once the plugin is registered. GStreamer refuses to load a
+    // plugin built for a different major version though, so all that can be
+    // reported is whether the plugin loads with the runtime API.
+    match plugin.load() {
+        Ok(_) => {
+            writeln!(
+                w,
+                "Plugin loads with runtime API {} ({})",
+                PROP_VALUE_COLOR.paint(&runtime_api),
+                PROP_ATTR_VALUE_COLOR.paint("compatible")
+            )?;
+            Ok(0)
+        }
+        Err(err) => {
+            writeln!(
+                w,
+                "{}",
+                PROP_VALUE_COLOR.paint(format!(
+                    "WARNING: plugin '{}' cannot be loaded by the GStreamer {} runtime: {}",
+                    plugin.plugin_name(),
+                    runtime_api,
+                    err
+                ))
+            )?;
+            Ok(1)
+        }
+    }
+}
+
+fn print_element_performance_category(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let klass: Vec<&str> = factory.klass().split('/').collect();
+    let zero_copy = factory.static_pad_templates().iter().any(|pad_tmpl| {
+        pad_tmpl.caps().iter_with_features().any(|(_, features)| {
+            features.contains("memory:DMABuf") || features.contains("memory:GLMemory")
+        })
+    });
+
+    let (category, reason) = if klass.contains(&"Hardware") {
+        ("hardware-accelerated", "klass contains \"Hardware\"")
+    } else if zero_copy {
+        ("zero-copy", "pad templates use DMA-BUF or GL memory")
+    } else if klass.contains(&"Network")
+        || (!factory.uri_protocols().is_empty()
+            && (klass.contains(&"Source") || klass.contains(&"Sink")))
+    {
+        ("I/O-bound", "network or URI source/sink")
+    } else {
+        ("cpu-bound", "software processing")
+    };
+
+    print_property(
+        w,
+        "Performance category",
+        &format!("{} ({})", PROP_VALUE_COLOR.paint(category), reason),
+        0,
+        0,
+        true,
+    )?;
+
+    Ok(0)
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Signals registered by the type itself, not the ones it inherits.
+fn signal_ids(type_: gst::glib::Type) -> Vec<gst::glib::subclass::signal::SignalId> {
+    let mut n_ids: u32 = 0;
+    unsafe {
+        let ids = gst::glib::gobject_ffi::g_signal_list_ids(type_.into_glib(), &mut n_ids);
+        let signal_ids = (0..n_ids as usize)
+            .map(|i| from_glib(*ids.add(i)))
+            .collect();
+        gst::glib::ffi::g_free(ids as gst::glib::ffi::gpointer);
+        signal_ids
+    }
+}
+
+fn gir_type_name(type_: gst::glib::Type) -> String {
+    let name = match type_ {
+        gst::glib::Type::UNIT => "none",
+        gst::glib::Type::BOOL => "gboolean",
+        gst::glib::Type::I8 => "gint8",
+        gst::glib::Type::U8 => "guint8",
+        gst::glib::Type::I32 => "gint",
+        gst::glib::Type::U32 => "guint",
+        gst::glib::Type::I_LONG => "glong",
+        gst::glib::Type::U_LONG => "gulong",
+        gst::glib::Type::I64 => "gint64",
+        gst::glib::Type::U64 => "guint64",
+        gst::glib::Type::F32 => "gfloat",
+        gst::glib::Type::F64 => "gdouble",
+        gst::glib::Type::STRING => "utf8",
+        gst::glib::Type::POINTER => "gpointer",
+        _ => type_.name(),
+    };
+    name.to_string()
+}
+
+fn write_gir_type(w: &mut dyn Write, type_: gst::glib::Type, indent: usize) -> io::Result<()> {
+    writeln!(
+        w,
+        "{}<type name=\"{}\"/>",
+        " ".repeat(indent),
+        xml_escape(&gir_type_name(type_))
+    )
+}
+
+// GIR splits type names into a namespace and a class name. Elements living in
+// the core library share the "Gst" prefix; anything else gets a namespace
+// named after its plugin since the real prefix can't be guessed.
+fn gir_namespace_and_class(type_name: &str, plugin_name: &str) -> (String, String) {
+    match type_name.strip_prefix("Gst") {
+        Some(class_name) if !class_name.is_empty() => ("Gst".to_string(), class_name.to_string()),
+        _ => {
+            let mut chars = plugin_name.chars();
+            let namespace = chars.next().map_or(String::new(), |first| {
+                first.to_uppercase().chain(chars).collect::<String>()
+            });
+            (namespace, type_name.to_string())
+        }
+    }
+}
+
+fn print_element_gir(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    let element = match factory.create_with_name(None) {
+        Ok(element) => element,
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let type_ = element.type_();
+    let plugin_name = factory
+        .plugin()
+        .map_or(String::new(), |plugin| plugin.plugin_name().to_string());
+    let (namespace, class_name) = gir_namespace_and_class(type_.name(), &plugin_name);
+    let parent = type_
+        .parent()
+        .map_or("GObject.Object".to_string(), |parent| {
+            let (parent_namespace, parent_class) = gir_namespace_and_class(parent.name(), "");
+            if parent_namespace == namespace || parent_namespace.is_empty() {
+                parent_class
+            } else {
+                format!("{}.{}", parent_namespace, parent_class)
+            }
+        });
+
+    writeln!(w, "<?xml version=\"1.0\"?>")?;
+    writeln!(
+        w,
+        "<repository version=\"1.2\" \
+         xmlns=\"http://www.gtk.org/introspection/core/1.0\" \
+         xmlns:c=\"http://www.gtk.org/introspection/c/1.0\" \
+         xmlns:glib=\"http://www.gtk.org/introspection/glib/1.0\">"
+    )?;
+    writeln!(w, "  <include name=\"Gst\" version=\"1.0\"/>")?;
+    writeln!(
+        w,
+        "  <namespace name=\"{}\" version=\"1.0\">",
+        xml_escape(&namespace)
+    )?;
+    writeln!(
+        w,
+        "    <class name=\"{}\" c:type=\"{}\" parent=\"{}\" glib:type-name=\"{}\">",
+        xml_escape(&class_name),
+        xml_escape(type_.name()),
+        xml_escape(&parent),
+        xml_escape(type_.name())
+    )?;
+
+    // Inherited properties and signals are described by the parent classes.
+    for pspec in element.list_properties().iter() {
+        if pspec.owner_type() != type_ {
+            continue;
+        }
+        let flags = pspec.flags();
+        let mut attrs = format!("name=\"{}\"", xml_escape(pspec.name()));
+        if flags.contains(gst::glib::ParamFlags::WRITABLE) {
+            attrs.push_str(" writable=\"1\"");
+        }
+        if !flags.contains(gst::glib::ParamFlags::READABLE) {
+            attrs.push_str(" readable=\"0\"");
+        }
+        if flags.contains(gst::glib::ParamFlags::CONSTRUCT_ONLY) {
+            attrs.push_str(" construct-only=\"1\"");
+        } else if flags.contains(gst::glib::ParamFlags::CONSTRUCT) {
+            attrs.push_str(" construct=\"1\"");
+        }
+        writeln!(w, "      <property {} transfer-ownership=\"none\">", attrs)?;
+        if let Some(blurb) = pspec.blurb() {
+            writeln!(w, "        <doc>{}</doc>", xml_escape(blurb))?;
+        }
+        write_gir_type(w, pspec.value_type(), 8)?;
+        writeln!(w, "      </property>")?;
+    }
+
+    for signal_id in signal_ids(type_) {
+        let query = signal_id.query();
+        let flags = query.flags();
+        let when = if flags.contains(gst::glib::SignalFlags::RUN_FIRST) {
+            "first"
+        } else if flags.contains(gst::glib::SignalFlags::RUN_CLEANUP) {
+            "cleanup"
+        } else {
+            "last"
+        };
+        let action = if flags.contains(gst::glib::SignalFlags::ACTION) {
+            " action=\"1\""
+        } else {
+            ""
+        };
+        writeln!(
+            w,
+            "      <glib:signal name=\"{}\" when=\"{}\"{}>",
+            xml_escape(query.signal_name()),
+            when,
+            action
+        )?;
+        writeln!(w, "        <return-value transfer-ownership=\"none\">")?;
+        write_gir_type(w, query.return_type().type_(), 10)?;
+        writeln!(w, "        </return-value>")?;
+        if !query.param_types().is_empty() {
+            writeln!(w, "        <parameters>")?;
+            for (i, param_type) in query.param_types().iter().enumerate() {
+                writeln!(
+                    w,
+                    "          <parameter name=\"arg{}\" transfer-ownership=\"none\">",
+                    i
+                )?;
+                write_gir_type(w, param_type.type_(), 12)?;
+                writeln!(w, "          </parameter>")?;
+            }
+            writeln!(w, "        </parameters>")?;
+        }
+        writeln!(w, "      </glib:signal>")?;
+    }
+
+    writeln!(w, "    </class>")?;
+    writeln!(w, "  </namespace>")?;
+    writeln!(w, "</repository>")?;
+
+    Ok(0)
+}
+
+fn print_element_memory_model(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let klass: Vec<&str> = factory.klass().split('/').collect();
+    let mut zero_copy_memory = false;
+    let mut video_meta = output_meta_types(&factory).contains(&"GstVideoMeta");
+    for pad_tmpl in factory.static_pad_templates() {
+        for (_, features) in pad_tmpl.caps().iter_with_features() {
+            if pad_tmpl.direction() == gst::PadDirection::Src
+                && (features.contains("memory:DMABuf") || features.contains("memory:GLMemory"))
+            {
+                zero_copy_memory = true;
+            }
+            if features.contains("meta:GstVideoMeta") {
+                video_meta = true;
+            }
+        }
+    }
+
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    print_property(
+        w,
+        "DMA-BUF/GL output",
+        yes_no(zero_copy_memory),
+        20,
+        0,
+        true,
+    )?;
+    print_property(w, "GstVideoMeta", yes_no(video_meta), 20, 0, true)?;
+    print_property(w, "Klass", factory.klass(), 20, 0, true)?;
+
+    let (model, reason) = if zero_copy_memory {
+        ("zero-copy capable", "source caps use DMA-BUF or GL memory")
+    } else if klass.contains(&"Converter") {
+        ("copy-required", "converters write into new buffers")
+    } else if klass.contains(&"Filter") {
+        ("zero-copy capable", "filters can pass buffers through")
+    } else if video_meta {
+        (
+            "zero-copy capable",
+            "GstVideoMeta describes foreign strides and offsets",
+        )
+    } else {
+        ("copy-required", "no zero-copy memory or meta support")
+    };
+    writeln!(w)?;
+    print_property(
+        w,
+        "Memory model",
+        &format!("{} ({})", PROP_VALUE_COLOR.paint(model), reason),
+        0,
+        0,
+        true,
+    )?;
+
+    Ok(0)
+}
+
+// Fields that are almost always a range of some sort and only clutter the
+// codec details.
+const AUTO_INSTALL_IGNORED_FIELDS: [&str; 8] = [
+    "pixel-aspect-ratio",
+    "framerate",
+    "channels",
+    "width",
+    "height",
+    "rate",
+    "depth",
+    "clock-rate",
+];
+
+// Lists what an element provides in the form used by the missing plugin
+// installer details: decoders, encoders, URI handlers and the element itself.
+fn auto_install_provides(factory: &gst::ElementFactory) -> Vec<String> {
+    let klass = factory.klass();
+    let mut provides = vec![format!("element-{}", factory.name())];
+
+    let codec = if ["Demuxer", "Decoder", "Depay", "Parser"]
+        .iter()
+        .any(|k| klass.contains(k))
+    {
+        Some(("decoder", gst::PadDirection::Sink))
+    } else if ["Muxer", "Encoder", "Pay"]
+        .iter()
+        .any(|k| klass.contains(k))
+    {
+        Some(("encoder", gst::PadDirection::Src))
+    } else {
+        None
+    };
+    if let Some((type_name, direction)) = codec {
+        for pad_tmpl in factory.static_pad_templates() {
+            if pad_tmpl.direction() != direction {
+                continue;
+            }
+            let caps = pad_tmpl.caps();
+            if caps.is_any() {
+                continue;
+            }
+            for structure in caps.iter() {
+                let mut structure = structure.to_owned();
+                structure.remove_fields(AUTO_INSTALL_IGNORED_FIELDS);
+                provides.push(format!("{}-{}", type_name, structure));
+            }
+        }
+    }
+
+    let uri_type_name = match factory.uri_type() {
+        gst::URIType::Src => Some("urisource"),
+        gst::URIType::Sink => Some("urisink"),
+        gst::URIType::Unknown => None,
+    };
+    if let Some(uri_type_name) = uri_type_name {
+        for protocol in factory.uri_protocols() {
+            provides.push(format!("{}-{}", uri_type_name, protocol));
+        }
+    }
+
+    provides
+}
+
+fn print_plugin_auto_install_info(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut factories: Vec<gst::ElementFactory> = registry
+        .features(gst::ElementFactory::static_type())
+        .into_iter()
+        .filter_map(|feature| feature.downcast::<gst::ElementFactory>().ok())
+        .collect();
+    factories.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+
+    for factory in &factories {
+        for detail in auto_install_provides(factory) {
+            writeln!(
+                w,
+                "gstreamer|1.0|gst-inspect-rs|{}|{}",
+                factory.longname(),
+                detail
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+const PIPELINE_EXAMPLES: &str = include_str!("pipeline_examples.toml");
+
+fn toml_string_list(entry: &toml::Table, key: &str) -> Vec<String> {
+    entry
+        .get(key)
+        .and_then(|value| value.as_array())
+        .map_or(Vec::new(), |values| {
+            values
+                .iter()
+                .filter_map(|value| value.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+}
+
+// Builds an example from the klass alone for elements without an entry in
+// pipeline_examples.toml.
+fn generic_pipeline_example(factory: &gst::ElementFactory) -> (&'static str, String) {
+    let klass = factory.klass();
+    let (src, convert, sink) = if klass.contains("Video") {
+        ("videotestsrc", Some("videoconvert"), "autovideosink")
+    } else if klass.contains("Audio") {
+        ("audiotestsrc", Some("audioconvert"), "autoaudiosink")
+    } else {
+        ("fakesrc", None, "fakesink")
+    };
+    let name = factory.name();
+
+    let (position, elements) = if klass.contains("Source") {
+        (
+            "source",
+            [Some(name.as_str()), convert, Some(sink)].to_vec(),
+        )
+    } else if klass.contains("Sink") {
+        ("sink", [Some(src), convert, Some(name.as_str())].to_vec())
+    } else {
+        (
+            "transform",
+            [Some(src), convert, Some(name.as_str()), convert, Some(sink)].to_vec(),
+        )
+    };
+    let elements: Vec<&str> = elements.into_iter().flatten().collect();
+
+    (position, format!("gst-launch-1.0 {}", elements.join(" ! ")))
+}
+
+fn print_pipeline_integration(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    let examples = match PIPELINE_EXAMPLES.parse::<toml::Table>() {
+        Ok(examples) => examples,
+        Err(err) => {
+            writeln!(w, "Could not read the pipeline examples: {}", err)?;
+            return Ok(-1);
+        }
+    };
+    let width = 20;
+
+    match examples
+        .get(element_name)
+        .and_then(|entry| entry.as_table())
+    {
+        Some(entry) => {
+            let position = entry.get("position").and_then(|v| v.as_str());
+            let launch = entry.get("launch").and_then(|v| v.as_str());
+            if let Some(position) = position {
+                print_property(w, "Pipeline position", position, width, 0, true)?;
+            }
+            if let Some(launch) = launch {
+                print_property(w, "Example", launch, width, 0, true)?;
+            }
+            for (label, key) in [
+                ("Typically after", "upstream"),
+                ("Typically before", "downstream"),
+            ] {
+                let elements = toml_string_list(entry, key);
+                if !elements.is_empty() {
+                    print_property(w, label, &elements.join(", "), width, 0, true)?;
+                }
+            }
+            if let Some(notes) = entry.get("notes").and_then(|v| v.as_str()) {
+                print_property(w, "Notes", notes, width, 0, true)?;
+            }
+        }
+        None => {
+            let (position, launch) = generic_pipeline_example(&factory);
+            print_property(w, "Pipeline position", position, width, 0, true)?;
+            print_property(w, "Example", &launch, width, 0, true)?;
+            print_property(
+                w,
+                "Notes",
+                "Generic example based on the klass, caps or properties may need to be set",
+                width,
+                0,
+                true,
+            )?;
+        }
+    }
+
+    Ok(0)
+}
+
+// Community-maintained notes on how well-known elements behave across
+// streaming threads. GStreamer does not expose this, so it has to be written
+// down by hand; corrections and additions are welcome upstream.
+const THREAD_SAFETY_NOTES_URL: &str = "https://github.com/cfoch/gst-inspect-rs";
+const THREAD_SAFETY_NOTES: [(&str, &str); 14] = [
+    (
+        "queue",
+        "Thread boundary: pushes downstream from its own streaming thread",
+    ),
+    (
+        "queue2",
+        "Thread boundary: pushes downstream from its own streaming thread",
+    ),
+    (
+        "multiqueue",
+        "Thread boundary: one streaming thread per source pad",
+    ),
+    (
+        "identity",
+        "Not safe for simultaneous pushes from several threads, put a queue in front",
+    ),
+    (
+        "tee",
+        "Pushes every branch from the upstream thread, add a queue after each branch",
+    ),
+    (
+        "funnel",
+        "Accepts pushes from several threads and serializes them",
+    ),
+    (
+        "input-selector",
+        "Serializes pushes from several threads, inactive sink pads block",
+    ),
+    (
+        "appsrc",
+        "push-buffer and the other action signals may be called from any thread",
+    ),
+    (
+        "appsink",
+        "pull-sample blocks the calling thread, callbacks run in the streaming thread",
+    ),
+    (
+        "fakesink",
+        "handoff signal handlers run in the streaming thread",
+    ),
+    ("valve", "The drop property may be toggled from any thread"),
+    (
+        "audiomixer",
+        "Aggregator: collects from each sink pad thread and outputs from its own thread",
+    ),
+    (
+        "compositor",
+        "Aggregator: collects from each sink pad thread and outputs from its own thread",
+    ),
+    (
+        "interleave",
+        "Collects from each sink pad thread, the last buffer to arrive pushes downstream",
+    ),
+];
+
+fn print_thread_safety(w: &mut dyn Write, factory: &gst::ElementFactory) -> io::Result<()> {
+    let note = THREAD_SAFETY_NOTES
+        .iter()
+        .find(|(name, _)| *name == factory.name().as_str())
+        .map_or(
+            "No thread-safety notes known for this element",
+            |(_, note)| *note,
+        );
+    print_property(w, "Thread safety", note, 0, 0, true)?;
+    writeln!(w)?;
+    writeln!(
+        w,
+        "These notes are community-maintained, contribute at {}",
+        hyperlink(THREAD_SAFETY_NOTES_URL, THREAD_SAFETY_NOTES_URL)
+    )?;
+
+    Ok(())
+}
+
+fn print_element_thread_safety(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    match gst::ElementFactory::find(element_name) {
+        Some(factory) => {
+            print_thread_safety(w, &factory)?;
+            Ok(0)
+        }
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            Ok(-1)
+        }
+    }
+}
+
+// Plugin set shipping some commonly used elements, used to suggest which
+// distribution package to install when they are missing.
+const ELEMENT_PLUGIN_SETS: [(&str, &str); 28] = [
+    ("playbin", "base"),
+    ("decodebin", "base"),
+    ("videoconvert", "base"),
+    ("audioconvert", "base"),
+    ("opusenc", "base"),
+    ("vorbisenc", "base"),
+    ("theoraenc", "base"),
+    ("vp8enc", "good"),
+    ("vp9enc", "good"),
+    ("flacenc", "good"),
+    ("lamemp3enc", "good"),
+    ("mpg123audiodec", "good"),
+    ("pulsesink", "good"),
+    ("rtspsrc", "good"),
+    ("souphttpsrc", "good"),
+    ("matroskamux", "good"),
+    ("qtmux", "good"),
+    ("h264parse", "bad"),
+    ("mpegtsmux", "bad"),
+    ("webrtcbin", "bad"),
+    ("srtsrc", "bad"),
+    ("srtsink", "bad"),
+    ("x265enc", "bad"),
+    ("x264enc", "ugly"),
+    ("mpeg2dec", "ugly"),
+    ("a52dec", "ugly"),
+    ("dvdreadsrc", "ugly"),
+    ("avdec_h264", "libav"),
+];
+
+fn detect_package_manager() -> Option<&'static str> {
+    if cfg!(target_os = "macos") {
+        return Some("brew");
+    }
+
+    let os_release = std::fs::read_to_string("/etc/os-release").ok()?;
+    let ids: Vec<&str> = os_release
+        .lines()
+        .filter(|line| line.starts_with("ID=") || line.starts_with("ID_LIKE="))
+        .flat_map(|line| {
+            line.split('=')
+                .nth(1)
+                .unwrap_or("")
+                .trim_matches('"')
+                .split(' ')
+        })
+        .collect();
+    if ids.iter().any(|id| ["debian", "ubuntu"].contains(id)) {
+        Some("apt")
+    } else if ids.iter().any(|id| ["fedora", "rhel"].contains(id)) {
+        Some("dnf")
+    } else if ids.contains(&"arch") {
+        Some("pacman")
+    } else {
+        None
+    }
+}
+
+fn install_command(package_manager: &str, plugin_set: &str) -> String {
+    match package_manager {
+        "apt" => match plugin_set {
+            "libav" => "apt install gstreamer1.0-libav".to_string(),
+            set => format!("apt install gstreamer1.0-plugins-{}", set),
+        },
+        "dnf" => match plugin_set {
+            "libav" => "dnf install gstreamer1-libav".to_string(),
+            "bad" | "ugly" => format!("dnf install gstreamer1-plugins-{}-free", plugin_set),
+            set => format!("dnf install gstreamer1-plugins-{}", set),
+        },
+        "pacman" => match plugin_set {
+            "libav" => "pacman -S gst-libav".to_string(),
+            set => format!("pacman -S gst-plugins-{}", set),
+        },
+        _ => "brew install gstreamer".to_string(),
+    }
+}
+
+fn print_install_command(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    if gst::ElementFactory::find(element_name).is_some() {
+        writeln!(w, "Element '{}' is already installed", element_name)?;
+        return Ok(0);
+    }
+
+    let plugin_set = if element_name.starts_with("avdec_") || element_name.starts_with("avenc_") {
+        Some("libav")
+    } else {
+        ELEMENT_PLUGIN_SETS
+            .iter()
+            .find(|(name, _)| *name == element_name)
+            .map(|(_, set)| *set)
+    };
+    let plugin_set = match plugin_set {
+        Some(plugin_set) => plugin_set,
+        None => {
+            writeln!(w, "No package known to provide element '{}'", element_name)?;
+            return Ok(1);
+        }
+    };
+
+    // Only an element missing from the database is an error, printing a
+    // suggestion is a success even though the element is not installed.
+    let package_manager = match detect_package_manager() {
+        Some(package_manager) => package_manager,
+        None => {
+            writeln!(
+                w,
+                "Element '{}' is missing, it is shipped in the {} plugin set",
+                ELEMENT_NAME_COLOR.paint(element_name),
+                PROP_VALUE_COLOR.paint(plugin_set)
+            )?;
+            writeln!(w, "Could not detect the package manager of this system")?;
+            return Ok(0);
+        }
+    };
+
+    writeln!(
+        w,
+        "Element '{}' is missing, install it with:",
+        ELEMENT_NAME_COLOR.paint(element_name)
+    )?;
+    writeln!(
+        w,
+        "  {}",
+        PROP_VALUE_COLOR.paint(install_command(package_manager, plugin_set))
+    )?;
+
+    Ok(0)
+}
+
+fn print_pipeline_validation(w: &mut dyn Write, pipeline: &str) -> io::Result<i32> {
+    match gst::parse_launch(pipeline) {
+        Ok(element) => {
+            let n_elements = match element.downcast_ref::<gst::Bin>() {
+                Some(bin) => bin
+                    .iterate_recurse()
+                    .into_iter()
+                    .filter(Result::is_ok)
+                    .count(),
+                None => 1,
+            };
+            writeln!(w, "{}", PROP_ATTR_VALUE_COLOR.paint("Valid pipeline"))?;
+            print_property(w, "Elements", &n_elements.to_string(), 0, 2, true)?;
+            Ok(0)
+        }
+        Err(err) => {
+            // The parser does not report where in the description it failed,
+            // only the kind of error and a message naming the culprit.
+            let kind = match err.kind::<gst::ParseError>() {
+                Some(gst::ParseError::Syntax) => "syntax error",
+                Some(gst::ParseError::NoSuchElement) => "no such element",
+                Some(gst::ParseError::NoSuchProperty) => "no such property",
+                Some(gst::ParseError::Link) => "link error",
+                Some(gst::ParseError::CouldNotSetProperty) => "could not set property",
+                Some(gst::ParseError::EmptyBin) => "empty bin",
+                Some(gst::ParseError::Empty) => "empty pipeline",
+                Some(gst::ParseError::DelayedLink) => "delayed link error",
+                _ => "error",
+            };
+            writeln!(w, "{}", PROP_VALUE_COLOR.paint("Invalid pipeline"))?;
+            print_property(w, "Error", kind, 0, 2, true)?;
+            print_property(w, "Message", err.message(), 0, 2, true)?;
+            Ok(1)
+        }
+    }
+}
+
+fn print_pipeline_element_summary(w: &mut dyn Write, element: &gst::Element) -> io::Result<()> {
+    let factory_name = element
+        .factory()
+        .map_or("(unknown)".to_string(), |factory| {
+            factory.name().to_string()
+        });
+    writeln!(
+        w,
+        "{} ({})",
+        ELEMENT_NAME_COLOR.paint(element.name().as_str()),
+        factory_name
+    )?;
+
+    for pad in element.pads() {
+        let peer = pad.peer().map_or("not linked".to_string(), |peer| {
+            peer.path_string().to_string()
+        });
+        let (arrow, color) = match pad.direction() {
+            gst::PadDirection::Src => ("->", SRC_PAD_COLOR),
+            _ => ("<-", SINK_PAD_COLOR),
+        };
+        writeln!(
+            w,
+            "  {} {} {}",
+            color.paint(format!("'{}'", pad.name())),
+            arrow,
+            peer
+        )?;
+        let caps = pad
+            .current_caps()
+            .map_or("not negotiated".to_string(), |caps| caps.to_string());
+        writeln!(w, "      {}", CAPS_TYPE_COLOR.paint(caps))?;
+    }
+
+    // Only the properties set in the description or changed by the element
+    // itself are of interest.
+    for pspec in element.list_properties().iter() {
+        let flags = pspec.flags();
+        if !flags.contains(gst::glib::ParamFlags::READABLE | gst::glib::ParamFlags::WRITABLE)
+            || ["name", "parent"].contains(&pspec.name())
+        {
+            continue;
+        }
+        let default = pspec.default_value().serialize().ok();
+        if let Ok(value) = element.property_value(pspec.name()).serialize() {
+            if Some(&value) != default.as_ref() {
+                print_property(w, pspec.name(), &value, 0, 2, true)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_pipeline_inspection(w: &mut dyn Write, description: &str) -> io::Result<i32> {
+    let pipeline = match gst::parse_launch(description) {
+        Ok(pipeline) => pipeline,
+        Err(err) => {
+            writeln!(w, "Invalid pipeline: {}", err.message())?;
+            return Ok(-1);
+        }
+    };
+    let bin = match pipeline.downcast_ref::<gst::Bin>() {
+        Some(bin) => bin.clone(),
+        None => {
+            print_pipeline_element_summary(w, &pipeline)?;
+            return Ok(0);
+        }
+    };
+
+    // Prerolling negotiates the caps shown for each pad.
+    let _ = pipeline.set_state(gst::State::Paused);
+    let _ = pipeline.state(Some(gst::ClockTime::SECOND));
+
+    // Sorted from sinks to sources, print them in data flow order instead.
+    let mut elements: Vec<gst::Element> = bin
+        .iterate_sorted()
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+    elements.reverse();
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            writeln!(w)?;
+        }
+        print_pipeline_element_summary(w, element)?;
+    }
+
+    let _ = pipeline.set_state(gst::State::Null);
+
+    Ok(0)
+}
+
+fn write_element_topology_graph(
+    w: &mut dyn Write,
+    element_name: &str,
+    path: &str,
+) -> io::Result<i32> {
+    let element = match gst::ElementFactory::make(element_name).build() {
+        Ok(element) => element,
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    let bin = match element.downcast_ref::<gst::Bin>() {
+        Some(bin) => bin.clone(),
+        None => {
+            writeln!(w, "Element '{}' is not a bin", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    // Feed the bin a test input when it has a sink pad so that elements
+    // which only build their internals once data flows get a chance to do so.
+    let pipeline = gst::Pipeline::new(None);
+    if pipeline.add(&element).is_err() {
+        writeln!(w, "Could not add '{}' to a pipeline", element_name)?;
+        return Ok(-1);
+    }
+    if !element.sink_pads().is_empty() {
+        if let Ok(src) = gst::ElementFactory::make("fakesrc")
+            .property("num-buffers", 1)
+            .build()
+        {
+            if pipeline.add(&src).is_ok() && src.link(&element).is_err() {
+                let _ = pipeline.remove(&src);
+            }
+        }
+    }
+
+    let _ = pipeline.set_state(gst::State::Playing);
+    let _ = pipeline.state(Some(gst::ClockTime::SECOND));
+    let dot = gst::debug_bin_to_dot_data(&bin, gst::DebugGraphDetails::all());
+    let _ = pipeline.set_state(gst::State::Null);
+
+    if let Err(err) = std::fs::write(path, dot.as_str()) {
+        writeln!(w, "Could not write '{}': {}", path, err)?;
+        return Ok(-1);
+    }
+    writeln!(
+        w,
+        "Wrote topology of '{}' to {}",
+        ELEMENT_NAME_COLOR.paint(element_name),
+        path
+    )?;
+
+    Ok(0)
+}
+
+// Surrounds the element with fake elements so that it gets data flowing.
+fn build_test_pipeline(element: &gst::Element) -> Option<gst::Pipeline> {
+    let pipeline = gst::Pipeline::new(None);
+    pipeline.add(element).ok()?;
+    if !element.sink_pads().is_empty() {
+        if let Ok(src) = gst::ElementFactory::make("fakesrc").build() {
+            if pipeline.add(&src).is_ok() && src.link(element).is_err() {
+                let _ = pipeline.remove(&src);
+            }
+        }
+    }
+    if !element.src_pads().is_empty() {
+        if let Ok(sink) = gst::ElementFactory::make("fakesink").build() {
+            if pipeline.add(&sink).is_ok() && element.link(&sink).is_err() {
+                let _ = pipeline.remove(&sink);
+            }
+        }
+    }
+
+    Some(pipeline)
+}
+
+fn watch_element_property(
+    w: &mut dyn Write,
+    element_name: &str,
+    property_name: &str,
+    interval: &str,
+) -> io::Result<i32> {
+    let interval = match interval.parse::<u64>() {
+        Ok(interval) if interval > 0 => std::time::Duration::from_millis(interval),
+        _ => {
+            writeln!(w, "Invalid interval '{}', expected milliseconds", interval)?;
+            return Ok(-1);
+        }
+    };
+    let element = match gst::ElementFactory::make(element_name).build() {
+        Ok(element) => element,
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    match element.find_property(property_name) {
+        Some(pspec) if pspec.flags().contains(gst::glib::ParamFlags::READABLE) => (),
+        Some(_) => {
+            writeln!(w, "Property '{}' is not readable", property_name)?;
+            return Ok(-1);
+        }
+        None => {
+            writeln!(
+                w,
+                "Element '{}' has no property '{}'",
+                element_name, property_name
+            )?;
+            return Ok(-1);
+        }
+    }
+
+    let pipeline = match build_test_pipeline(&element) {
+        Some(pipeline) => pipeline,
+        None => {
+            writeln!(w, "Could not add '{}' to a pipeline", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    if pipeline.set_state(gst::State::Playing).is_err() {
+        writeln!(w, "Could not set '{}' to PLAYING", element_name)?;
+        let _ = pipeline.set_state(gst::State::Null);
+        return Ok(-1);
+    }
+    writeln!(
+        w,
+        "Watching {}::{} every {} ms, press Ctrl-C to stop",
+        ELEMENT_NAME_COLOR.paint(element_name),
+        PROP_NAME_COLOR.paint(property_name),
+        interval.as_millis()
+    )?;
+    w.flush()?;
+
+    let start = std::time::Instant::now();
+    let mut last_value: Option<String> = None;
+    loop {
+        let value = element.property_value(property_name);
+        let value = value
+            .serialize()
+            .map_or("(unserializable)".to_string(), |s| s.to_string());
+        if last_value.as_ref() != Some(&value) {
+            writeln!(
+                w,
+                "[{:>10.3}s] {}",
+                start.elapsed().as_secs_f64(),
+                PROP_VALUE_COLOR.paint(&value)
+            )?;
+            w.flush()?;
+            last_value = Some(value);
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+// A freshly created element should hold the defaults its param specs declare.
+// GstObject's own properties are skipped, the name gets assigned on creation.
+fn check_property_defaults(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let element = match gst::ElementFactory::make(element_name).build() {
+        Ok(element) => element,
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let mut st = 0;
+    for pspec in element.list_properties().iter() {
+        if !pspec.flags().contains(gst::glib::ParamFlags::READABLE)
+            || pspec.owner_type() == gst::Object::static_type()
+        {
+            continue;
+        }
+        let value = element.property_value(pspec.name()).serialize();
+        let default = pspec.default_value().serialize();
+        if let (Ok(value), Ok(default)) = (value, default) {
+            if value != default {
+                writeln!(
+                    w,
+                    "{}: property '{}' reads {} at creation but default is {}",
+                    REMOVED_COLOR.paint("WARNING"),
+                    pspec.name(),
+                    value,
+                    default
+                )?;
+                st = 1;
+            }
+        }
+    }
+
+    Ok(st)
+}
+
+fn check_element_pad_count(
+    w: &mut dyn Write,
+    element_name: &str,
+    expected_src: &str,
+    expected_sink: &str,
+) -> io::Result<i32> {
+    let (expected_src, expected_sink) = match (
+        expected_src.parse::<usize>(),
+        expected_sink.parse::<usize>(),
+    ) {
+        (Ok(src), Ok(sink)) => (src, sink),
+        _ => {
+            writeln!(w, "Pad counts must be non-negative integers")?;
+            return Ok(-1);
+        }
+    };
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let count_always = |direction: gst::PadDirection| {
+        factory
+            .static_pad_templates()
+            .iter()
+            .filter(|pad_tmpl| {
+                pad_tmpl.direction() == direction && pad_tmpl.presence() == gst::PadPresence::Always
+            })
+            .count()
+    };
+    let actual_src = count_always(gst::PadDirection::Src);
+    let actual_sink = count_always(gst::PadDirection::Sink);
+
+    for (label, color, expected, actual) in [
+        ("SRC pads", SRC_PAD_COLOR, expected_src, actual_src),
+        ("SINK pads", SINK_PAD_COLOR, expected_sink, actual_sink),
+    ] {
+        let status = if expected == actual { "ok" } else { "MISMATCH" };
+        print_colored_property(
+            w,
+            label,
+            color,
+            &format!(
+                "expected {}, actual {} ({})",
+                expected,
+                actual,
+                PROP_ATTR_VALUE_COLOR.paint(status)
+            ),
+            10,
+            2,
+            true,
+        )?;
+    }
+
+    Ok(
+        if actual_src == expected_src && actual_sink == expected_sink {
+            0
+        } else {
+            1
+        },
+    )
+}
+
+const SQL_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS plugins (
+    name TEXT PRIMARY KEY,
+    description TEXT,
+    filename TEXT,
+    version TEXT,
+    license TEXT,
+    source TEXT,
+    package TEXT,
+    origin TEXT
+);
+CREATE TABLE IF NOT EXISTS elements (
+    name TEXT PRIMARY KEY,
+    plugin TEXT REFERENCES plugins (name),
+    long_name TEXT,
+    klass TEXT,
+    description TEXT,
+    author TEXT,
+    rank INTEGER
+);
+CREATE TABLE IF NOT EXISTS pad_templates (
+    element TEXT REFERENCES elements (name),
+    name_template TEXT,
+    direction TEXT,
+    presence TEXT,
+    caps TEXT,
+    PRIMARY KEY (element, name_template)
+);
+CREATE TABLE IF NOT EXISTS properties (
+    element TEXT REFERENCES elements (name),
+    name TEXT,
+    type TEXT,
+    blurb TEXT,
+    readable BOOLEAN,
+    writable BOOLEAN,
+    PRIMARY KEY (element, name)
+);
+";
+
+fn sql_string(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("'{}'", value.replace('\'', "''")),
+        None => "NULL".to_string(),
+    }
+}
+
+fn print_registry_sql(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut plugins = registry.plugins();
+
+    writeln!(w, "{}", SQL_SCHEMA)?;
+
+    plugins.sort_by(|p1, p2| p1.plugin_name().as_str().cmp(p2.plugin_name().as_str()));
+    for plugin in &plugins {
+        let filename = plugin.filename().map(|f| f.to_string_lossy().into_owned());
+        writeln!(
+            w,
+            "INSERT INTO plugins (name, description, filename, version, license, source, package, origin) VALUES ({}, {}, {}, {}, {}, {}, {}, {});",
+            sql_string(Some(plugin.plugin_name().as_str())),
+            sql_string(Some(plugin.description().as_str())),
+            sql_string(filename.as_deref()),
+            sql_string(Some(plugin.version().as_str())),
+            sql_string(Some(plugin.license().as_str())),
+            sql_string(Some(plugin.source().as_str())),
+            sql_string(Some(plugin.package().as_str())),
+            sql_string(Some(plugin.origin().as_str())),
+        )?;
+
+        let mut features = registry.features_by_plugin(&plugin.plugin_name());
+        features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+        for feature in &features {
+            let factory = match feature.downcast_ref::<gst::ElementFactory>() {
+                Some(factory) => factory,
+                None => continue,
+            };
+            let element_name = sql_string(Some(factory.name().as_str()));
+            writeln!(
+                w,
+                "INSERT INTO elements (name, plugin, long_name, klass, description, author, rank) VALUES ({}, {}, {}, {}, {}, {}, {});",
+                element_name,
+                sql_string(Some(plugin.plugin_name().as_str())),
+                sql_string(Some(factory.longname())),
+                sql_string(Some(factory.klass())),
+                sql_string(Some(factory.description())),
+                sql_string(Some(factory.author())),
+                factory.rank().into_glib(),
+            )?;
+
+            for pad_tmpl in factory.static_pad_templates() {
+                let direction = match pad_tmpl.direction() {
+                    gst::PadDirection::Src => "src",
+                    gst::PadDirection::Sink => "sink",
+                    gst::PadDirection::Unknown => "unknown",
+                };
+                let presence = match pad_tmpl.presence() {
+                    gst::PadPresence::Always => "always",
+                    gst::PadPresence::Sometimes => "sometimes",
+                    gst::PadPresence::Request => "request",
+                };
+                writeln!(
+                    w,
+                    "INSERT INTO pad_templates (element, name_template, direction, presence, caps) VALUES ({}, {}, {}, {}, {});",
+                    element_name,
+                    sql_string(Some(pad_tmpl.name_template())),
+                    sql_string(Some(direction)),
+                    sql_string(Some(presence)),
+                    sql_string(Some(&pad_tmpl.caps().to_string())),
+                )?;
+            }
+
+            let element = match factory.create_with_name(None) {
+                Ok(element) => element,
+                Err(_) => continue,
+            };
+            for pspec in element.list_properties().iter() {
+                writeln!(
+                    w,
+                    "INSERT INTO properties (element, name, type, blurb, readable, writable) VALUES ({}, {}, {}, {}, {}, {});",
+                    element_name,
+                    sql_string(Some(pspec.name())),
+                    sql_string(Some(pspec.value_type().name())),
+                    sql_string(pspec.blurb()),
+                    pspec.flags().contains(gst::glib::ParamFlags::READABLE),
+                    pspec.flags().contains(gst::glib::ParamFlags::WRITABLE),
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+const AVRO_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "ElementInfo",
+    "namespace": "org.freedesktop.gstreamer",
+    "fields": [
+        {"name": "name", "type": "string"},
+        {"name": "plugin", "type": "string"},
+        {"name": "long_name", "type": "string"},
+        {"name": "klass", "type": "string"},
+        {"name": "description", "type": "string"},
+        {"name": "author", "type": "string"},
+        {"name": "rank", "type": "int"},
+        {"name": "pad_templates", "type": {"type": "array", "items": {
+            "type": "record",
+            "name": "PadTemplate",
+            "fields": [
+                {"name": "name_template", "type": "string"},
+                {"name": "direction", "type": "string"},
+                {"name": "presence", "type": "string"},
+                {"name": "caps", "type": "string"}
+            ]
+        }}},
+        {"name": "properties", "type": {"type": "array", "items": {
+            "type": "record",
+            "name": "Property",
+            "fields": [
+                {"name": "name", "type": "string"},
+                {"name": "type", "type": "string"},
+                {"name": "blurb", "type": ["null", "string"]},
+                {"name": "readable", "type": "boolean"},
+                {"name": "writable", "type": "boolean"},
+                {"name": "default", "type": ["null", "string"]}
+            ]
+        }}}
+    ]
+}"#;
+
+fn avro_nullable_string(value: Option<&str>) -> apache_avro::types::Value {
+    use apache_avro::types::Value;
+
+    match value {
+        Some(value) => Value::Union(1, Box::new(Value::String(value.to_string()))),
+        None => Value::Union(0, Box::new(Value::Null)),
+    }
+}
+
+fn avro_element_info(
+    plugin: &gst::Plugin,
+    factory: &gst::ElementFactory,
+) -> Vec<(String, apache_avro::types::Value)> {
+    use apache_avro::types::Value;
+
+    let string = |s: &str| Value::String(s.to_string());
+    let pad_templates = factory
+        .static_pad_templates()
+        .iter()
+        .map(|pad_tmpl| {
+            let direction = match pad_tmpl.direction() {
+                gst::PadDirection::Src => "src",
+                gst::PadDirection::Sink => "sink",
+                gst::PadDirection::Unknown => "unknown",
+            };
+            let presence = match pad_tmpl.presence() {
+                gst::PadPresence::Always => "always",
+                gst::PadPresence::Sometimes => "sometimes",
+                gst::PadPresence::Request => "request",
+            };
+            Value::Record(vec![
+                (
+                    "name_template".to_string(),
+                    string(pad_tmpl.name_template()),
+                ),
+                ("direction".to_string(), string(direction)),
+                ("presence".to_string(), string(presence)),
+                ("caps".to_string(), string(&pad_tmpl.caps().to_string())),
+            ])
+        })
+        .collect();
+    let properties = factory
+        .create_with_name(None)
+        .map_or(Vec::new(), |element| {
+            element
+                .list_properties()
+                .iter()
+                .map(|pspec| {
+                    let default = pspec.default_value().serialize().ok();
+                    Value::Record(vec![
+                        ("name".to_string(), string(pspec.name())),
+                        ("type".to_string(), string(pspec.value_type().name())),
+                        ("blurb".to_string(), avro_nullable_string(pspec.blurb())),
+                        (
+                            "readable".to_string(),
+                            Value::Boolean(pspec.flags().contains(gst::glib::ParamFlags::READABLE)),
+                        ),
+                        (
+                            "writable".to_string(),
+                            Value::Boolean(pspec.flags().contains(gst::glib::ParamFlags::WRITABLE)),
+                        ),
+                        (
+                            "default".to_string(),
+                            avro_nullable_string(default.as_deref()),
+                        ),
+                    ])
+                })
+                .collect()
+        });
+
+    vec![
+        ("name".to_string(), string(factory.name().as_str())),
+        ("plugin".to_string(), string(plugin.plugin_name().as_str())),
+        ("long_name".to_string(), string(factory.longname())),
+        ("klass".to_string(), string(factory.klass())),
+        ("description".to_string(), string(factory.description())),
+        ("author".to_string(), string(factory.author())),
+        ("rank".to_string(), Value::Int(factory.rank().into_glib())),
+        ("pad_templates".to_string(), Value::Array(pad_templates)),
+        ("properties".to_string(), Value::Array(properties)),
+    ]
+}
+
+// Writes every element of the registry as an Avro object container file.
+fn write_registry_avro(w: &mut dyn Write) -> io::Result<()> {
+    let schema = apache_avro::Schema::parse_str(AVRO_SCHEMA).expect("invalid Avro schema");
+    let mut writer = apache_avro::Writer::new(&schema, w);
+
+    let registry = gst::Registry::get();
+    let mut plugins = registry.plugins();
+    plugins.sort_by(|p1, p2| p1.plugin_name().as_str().cmp(p2.plugin_name().as_str()));
+    for plugin in &plugins {
+        let mut features = registry.features_by_plugin(&plugin.plugin_name());
+        features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+        for feature in &features {
+            if let Some(factory) = feature.downcast_ref::<gst::ElementFactory>() {
+                let record = apache_avro::types::Value::Record(avro_element_info(plugin, factory));
+                writer.append(record).map_err(io::Error::other)?;
+            }
+        }
+    }
+    writer.flush().map_err(io::Error::other)?;
+
+    Ok(())
+}
+
+fn property_type_category(pspec: &gst::glib::ParamSpec) -> String {
+    let value_type = pspec.value_type();
+    if [
+        gst::glib::Type::I32,
+        gst::glib::Type::U32,
+        gst::glib::Type::I_LONG,
+        gst::glib::Type::U_LONG,
+        gst::glib::Type::I64,
+        gst::glib::Type::U64,
+        gst::glib::Type::I8,
+        gst::glib::Type::U8,
+    ]
+    .contains(&value_type)
+    {
+        "Integer".to_string()
+    } else if value_type == gst::glib::Type::F32 || value_type == gst::glib::Type::F64 {
+        "Float".to_string()
+    } else if value_type == gst::glib::Type::BOOL {
+        "Boolean".to_string()
+    } else if value_type == gst::glib::Type::STRING {
+        "String".to_string()
+    } else if value_type.is_a(gst::glib::Type::ENUM) {
+        "Enum".to_string()
+    } else if value_type.is_a(gst::glib::Type::FLAGS) {
+        "Flags".to_string()
+    } else if value_type == gst::Fraction::static_type() {
+        "Fraction".to_string()
+    } else if value_type.is_a(gst::glib::Type::OBJECT) {
+        "Object".to_string()
+    } else if value_type.is_a(gst::glib::Type::BOXED) {
+        "Boxed".to_string()
+    } else {
+        value_type.name().to_string()
+    }
+}
+
+fn count_property_types(element: &gst::Element, counts: &mut HashMap<String, usize>) {
+    for pspec in element.list_properties().iter() {
+        *counts.entry(property_type_category(pspec)).or_insert(0) += 1;
+    }
+}
+
+fn sorted_property_type_counts(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|(type1, n1), (type2, n2)| n2.cmp(n1).then_with(|| type1.cmp(type2)));
+    counts
+}
+
+fn print_property_type_count(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let element = match gst::ElementFactory::make(element_name).build() {
+        Ok(element) => element,
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let mut counts = HashMap::new();
+    count_property_types(&element, &mut counts);
+    let summary: Vec<String> = sorted_property_type_counts(counts)
+        .iter()
+        .map(|(type_name, n)| format!("{}: {}", DATA_TYPE_COLOR.paint(type_name), n))
+        .collect();
+    writeln!(w, "{}", summary.join(", "))?;
+
+    Ok(0)
+}
+
+fn print_all_property_type_counts(w: &mut dyn Write) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut counts = HashMap::new();
+
+    for feature in registry.features(gst::ElementFactory::static_type()) {
+        let factory = match feature.downcast::<gst::ElementFactory>() {
+            Ok(factory) => factory,
+            Err(_) => continue,
+        };
+        if let Ok(element) = factory.create_with_name(None) {
+            count_property_types(&element, &mut counts);
+        }
+    }
+
+    let counts = sorted_property_type_counts(counts);
+    let max = counts.first().map_or(1, |(_, n)| *n);
+    let bar_width = 50;
+    for (type_name, n) in &counts {
+        writeln!(
+            w,
+            "{} {} {}",
+            DATA_TYPE_COLOR.paint(format!("{:<12}", type_name)),
+            PROP_VALUE_COLOR.paint("#".repeat((n * bar_width / max).max(1))),
+            n
+        )?;
+    }
+
+    Ok(())
+}
+
+fn check_element_has_interface(
+    w: &mut dyn Write,
+    element_name: &str,
+    interface_name: &str,
+) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name).map(|factory| factory.load()) {
+        Some(Ok(factory)) => factory,
+        _ => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(2);
+        }
+    };
+    let interface = match gst::glib::Type::from_name(interface_name) {
+        Some(interface) if interface.is_a(gst::glib::Type::INTERFACE) => interface,
+        _ => {
+            writeln!(w, "Unknown interface '{}'", interface_name)?;
+            return Ok(3);
+        }
+    };
+
+    if factory.element_type().is_a(interface) {
+        writeln!(w, "{}", PROP_ATTR_VALUE_COLOR.paint("YES"))?;
+        Ok(0)
+    } else {
+        writeln!(w, "{}", PROP_VALUE_COLOR.paint("NO"))?;
+        Ok(1)
+    }
+}
+
+// The caps of an element count as broad once more than this many fields use
+// ranges, so up to three ranges, e.g. width, height and framerate, are fine.
+const BROAD_CAPS_RANGE_FIELDS: usize = 3;
+// Lists of up to this many values are still an explicit enumeration and do
+// not make the caps less strict.
+const STRICT_CAPS_LIST_LEN: usize = 8;
+
+fn caps_field_breadth(value: &gst::glib::SendValue) -> Option<&'static str> {
+    match value.type_().name() {
+        "GstIntRange" | "GstInt64Range" | "GstDoubleRange" | "GstFractionRange" => Some("range"),
+        "GstValueList" => Some("list"),
+        _ => None,
+    }
+}
+
+fn check_caps_strict(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let mut n_ranges = 0;
+    let mut n_flagged = 0;
+    let mut pad_templates = factory.static_pad_templates().clone();
+    pad_templates.sort_by(|t1, t2| t1.name_template().cmp(t2.name_template()));
+    for pad_tmpl in pad_templates {
+        let caps = pad_tmpl.caps();
+        let name_template = pad_tmpl.name_template().to_string();
+        if caps.is_any() {
+            writeln!(
+                w,
+                "{}: {}",
+                PROP_NAME_COLOR.paint(&name_template),
+                PROP_VALUE_COLOR.paint("ANY caps")
+            )?;
+            n_flagged += 1;
+            continue;
+        }
+        for structure in caps.iter() {
+            for (field, value) in structure.iter() {
+                let breadth = match caps_field_breadth(value) {
+                    Some(breadth) => breadth,
+                    None => continue,
+                };
+                let small_list = value
+                    .get::<gst::List>()
+                    .map_or(false, |list| list.len() <= STRICT_CAPS_LIST_LEN);
+                if breadth == "range" {
+                    n_ranges += 1;
+                }
+                if !small_list {
+                    n_flagged += 1;
+                }
+                writeln!(
+                    w,
+                    "{}: {}: {} is a {}{}",
+                    PROP_NAME_COLOR.paint(&name_template),
+                    STRUCT_NAME_COLOR.paint(structure.name().as_str()),
+                    FIELD_NAME_COLOR.paint(field.as_str()),
+                    if small_list { "small " } else { "" },
+                    breadth
+                )?;
+            }
+        }
+    }
+
+    if n_ranges > BROAD_CAPS_RANGE_FIELDS {
+        writeln!(w)?;
+        writeln!(
+            w,
+            "{}",
+            PROP_VALUE_COLOR.paint(format!(
+                "WARNING: Broad caps — {} fields use ranges",
+                n_ranges
+            ))
+        )?;
+    }
+    if n_flagged == 0 {
+        writeln!(
+            w,
+            "'{}' has strict caps",
+            ELEMENT_NAME_COLOR.paint(element_name)
+        )?;
+        return Ok(0);
+    }
+
+    Ok(1)
+}
+
+fn registry_snapshot() -> HashMap<String, HashSet<String>> {
+    let registry = gst::Registry::get();
+
+    registry
+        .plugins()
+        .iter()
+        .map(|plugin| {
+            let features = registry
+                .features_by_plugin(&plugin.plugin_name())
+                .iter()
+                .map(|feature| feature.name().to_string())
+                .collect();
+            (plugin.plugin_name().to_string(), features)
+        })
+        .collect()
+}
+
+fn print_registry_diff(
+    w: &mut dyn Write,
+    old: &HashMap<String, HashSet<String>>,
+    new: &HashMap<String, HashSet<String>>,
+) -> io::Result<()> {
+    let mut plugin_names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    plugin_names.sort();
+    plugin_names.dedup();
+
+    for plugin_name in plugin_names {
+        match (old.get(plugin_name), new.get(plugin_name)) {
+            (None, Some(features)) => writeln!(
+                w,
+                "{} {} ({} features)",
+                ADDED_COLOR.paint("+"),
+                PLUGIN_NAME_COLOR.paint(plugin_name),
+                features.len()
+            )?,
+            (Some(_), None) => writeln!(
+                w,
+                "{} {}",
+                REMOVED_COLOR.paint("-"),
+                PLUGIN_NAME_COLOR.paint(plugin_name)
+            )?,
+            (Some(old_features), Some(new_features)) if old_features != new_features => {
+                writeln!(w, "~ {}", PLUGIN_NAME_COLOR.paint(plugin_name))?;
+                let mut added: Vec<&String> = new_features.difference(old_features).collect();
+                let mut removed: Vec<&String> = old_features.difference(new_features).collect();
+                added.sort();
+                removed.sort();
+                for feature in added {
+                    writeln!(w, "  {} {}", ADDED_COLOR.paint("+"), feature)?;
+                }
+                for feature in removed {
+                    writeln!(w, "  {} {}", REMOVED_COLOR.paint("-"), feature)?;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn watch_registry(w: &mut dyn Write, interval: u64) -> io::Result<i32> {
+    let interval = std::time::Duration::from_secs(interval.max(1));
+    let mut snapshot = registry_snapshot();
+
+    writeln!(
+        w,
+        "Watching {} plugins every {} s, press Ctrl-C to stop",
+        snapshot.len(),
+        interval.as_secs()
+    )?;
+    w.flush()?;
+
+    loop {
+        std::thread::sleep(interval);
+        // There is no way to rescan a single registry on demand, so
+        // re-run the same update done at initialization.
+        if let Err(err) = gst::update_registry() {
+            writeln!(w, "Could not update the registry: {}", err)?;
+            w.flush()?;
+            continue;
+        }
+
+        let new_snapshot = registry_snapshot();
+        if new_snapshot != snapshot {
+            writeln!(w)?;
+            writeln!(
+                w,
+                "{}",
+                HEADING_COLOR.paint(format!(
+                    "Registry changes ({}):",
+                    format_utc_timestamp(std::time::SystemTime::now()).unwrap_or_default()
+                ))
+            )?;
+            print_registry_diff(w, &snapshot, &new_snapshot)?;
+            w.flush()?;
+            snapshot = new_snapshot;
+        }
+    }
+}
+
+fn print_plugin_path(w: &mut dyn Write) -> io::Result<()> {
+    for var in ["GST_PLUGIN_PATH", "GST_PLUGIN_SYSTEM_PATH"] {
+        let value = std::env::var(var).unwrap_or_else(|_| "(unset)".to_string());
+        print_property(w, var, &value, 25, 0, true)?;
+    }
+
+    // The compiled-in plugin directory is not exposed, so derive the
+    // directories that were actually scanned from the loaded plugins.
+    let mut dirs: Vec<String> = gst::Registry::get()
+        .plugins()
+        .iter()
+        .filter_map(|plugin| plugin.filename())
+        .filter_map(|filename| filename.parent().map(|dir| dir.display().to_string()))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+
+    writeln!(w)?;
+    writeln!(w, "{}", HEADING_COLOR.paint("Plugin directories:"))?;
+    if dirs.is_empty() {
+        writeln!(w, "{}{}", &" ".repeat(2), "none")?;
+    }
+    for dir in &dirs {
+        writeln!(w, "  {}", PROP_VALUE_COLOR.paint(dir))?;
+    }
+
+    Ok(())
+}
+
+fn signal_arg_type_name(type_: gst::glib::Type) -> String {
+    if type_.is_a(gst::glib::Type::OBJECT)
+        || type_.is_a(gst::glib::Type::BOXED)
+        || type_.is_a(gst::glib::Type::INTERFACE)
+    {
+        format!("{} *", type_.name())
+    } else {
+        type_.name().to_string()
+    }
+}
+
+fn format_signal_prototype(
+    instance_type: gst::glib::Type,
+    query: &gst::glib::subclass::signal::SignalQuery,
+) -> String {
+    let mut args = vec![format!("{} object", signal_arg_type_name(instance_type))];
+    for (i, param_type) in query.param_types().iter().enumerate() {
+        args.push(format!(
+            "{} arg{}",
+            signal_arg_type_name(param_type.type_()),
+            i
+        ));
+    }
+    args.push("gpointer user_data".to_string());
+
+    format!(
+        "{} user_function ({});",
+        signal_arg_type_name(query.return_type().type_()),
+        args.join(", ")
+    )
+}
+
+fn print_all_signals(w: &mut dyn Write, signal_name: &str) -> io::Result<()> {
+    let registry = gst::Registry::get();
+    let mut factories: Vec<gst::ElementFactory> = registry
+        .features(gst::ElementFactory::static_type())
+        .into_iter()
+        .filter_map(|feature| feature.downcast::<gst::ElementFactory>().ok())
+        .collect();
+    factories.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+
+    for factory in &factories {
+        let element = match factory.create_with_name(None) {
+            Ok(element) => element,
+            Err(_) => continue,
+        };
+        let signal_id =
+            match gst::glib::subclass::signal::SignalId::lookup(signal_name, element.type_()) {
+                Some(signal_id) => signal_id,
+                None => continue,
+            };
+        writeln!(
+            w,
+            "{}: {}",
+            ELEMENT_NAME_COLOR.paint(factory.name().to_string()),
+            format_signal_prototype(element.type_(), &signal_id.query())
+        )?;
+    }
+
+    Ok(())
+}
+
+fn check_all_plugins(w: &mut dyn Write) -> io::Result<i32> {
+    let mut plugins = gst::Registry::get().plugins();
+    let mut st = 0;
+
+    plugins.sort_by(|p1, p2| p1.plugin_name().as_str().cmp(p2.plugin_name().as_str()));
+    for plugin in &plugins {
+        let loaded = if plugin.is_loaded() {
+            Ok(())
+        } else {
+            match plugin.filename() {
+                Some(filename) => gst::Plugin::load_file(filename)
+                    .map(|_| ())
+                    .map_err(|err| err.to_string()),
+                None => plugin.load().map(|_| ()).map_err(|err| err.to_string()),
+            }
+        };
+
+        match loaded {
+            Ok(()) => writeln!(
+                w,
+                "{} {}",
+                PROP_ATTR_VALUE_COLOR.paint("OK"),
+                PLUGIN_NAME_COLOR.paint(plugin.plugin_name().as_str())
+            )?,
+            Err(reason) => {
+                writeln!(
+                    w,
+                    "{} {}: {}",
+                    PROP_VALUE_COLOR.paint("FAIL"),
+                    PLUGIN_NAME_COLOR.paint(plugin.plugin_name().as_str()),
+                    reason
+                )?;
+                st = 1;
+            }
+        }
+    }
+
+    Ok(st)
+}
+
+fn pspec_range(pspec: &gst::glib::ParamSpec) -> Option<String> {
+    use gst::glib::{
+        ParamSpecDouble, ParamSpecFloat, ParamSpecInt, ParamSpecInt64, ParamSpecUInt,
+        ParamSpecUInt64,
+    };
+
+    if let Some(pspec) = pspec.downcast_ref::<ParamSpecInt>() {
+        Some(format!("{}..{}", pspec.minimum(), pspec.maximum()))
+    } else if let Some(pspec) = pspec.downcast_ref::<ParamSpecUInt>() {
+        Some(format!("{}..{}", pspec.minimum(), pspec.maximum()))
+    } else if let Some(pspec) = pspec.downcast_ref::<ParamSpecInt64>() {
+        Some(format!("{}..{}", pspec.minimum(), pspec.maximum()))
+    } else if let Some(pspec) = pspec.downcast_ref::<ParamSpecUInt64>() {
+        Some(format!("{}..{}", pspec.minimum(), pspec.maximum()))
+    } else if let Some(pspec) = pspec.downcast_ref::<ParamSpecFloat>() {
+        Some(format!("{}..{}", pspec.minimum(), pspec.maximum()))
+    } else {
+        pspec
+            .downcast_ref::<ParamSpecDouble>()
+            .map(|pspec| format!("{}..{}", pspec.minimum(), pspec.maximum()))
+    }
+}
+
+fn describe_pspec(pspec: &gst::glib::ParamSpec) -> String {
+    let default = pspec
+        .default_value()
+        .serialize()
+        .map_or("?".to_string(), |default| default.to_string());
+    match pspec_range(pspec) {
+        Some(range) => format!("{} [{}] = {}", pspec.value_type().name(), range, default),
+        None => format!("{} = {}", pspec.value_type().name(), default),
+    }
+}
+
+fn print_element_comparison(w: &mut dyn Write, name1: &str, name2: &str) -> io::Result<i32> {
+    let mut properties = Vec::new();
+    for name in [name1, name2] {
+        let element = match gst::ElementFactory::make(name).build() {
+            Ok(element) => element,
+            Err(_) => {
+                writeln!(w, "Could not create element '{}'", name)?;
+                return Ok(-1);
+            }
+        };
+        let pspecs: HashMap<String, gst::glib::ParamSpec> = element
+            .list_properties()
+            .iter()
+            .map(|pspec| (pspec.name().to_string(), pspec.clone()))
+            .collect();
+        properties.push(pspecs);
+    }
+
+    let mut names: Vec<&String> = properties[0].keys().chain(properties[1].keys()).collect();
+    names.sort();
+    names.dedup();
+
+    let (name_width, column_width) = (24, 40);
+    writeln!(
+        w,
+        "{} | {} | {}",
+        HEADING_COLOR.paint(format!("{:<name_width$}", "Property")),
+        HEADING_COLOR.paint(format!("{:<column_width$}", name1)),
+        HEADING_COLOR.paint(name2)
+    )?;
+    writeln!(w, "{}", "-".repeat(name_width + 2 * column_width + 6))?;
+
+    for name in names {
+        let descriptions: Vec<String> = properties
+            .iter()
+            .map(|pspecs| pspecs.get(name).map_or("-".to_string(), describe_pspec))
+            .collect();
+        let color = if descriptions[0] == descriptions[1] {
+            PROP_NAME_COLOR
+        } else {
+            PROP_VALUE_COLOR
+        };
+        writeln!(
+            w,
+            "{} | {:<column_width$} | {}",
+            color.paint(format!("{:<name_width$}", name)),
+            descriptions[0],
+            descriptions[1]
+        )?;
+    }
+
+    Ok(0)
+}
+
+const CAPS_NEGOTIATION_RUNS: u32 = 100;
+
+// Linking checks the caps of both pads for compatibility, so relinking the
+// same pair times the caps queries and intersections each element answers.
+fn measure_caps_negotiation_time(w: &mut dyn Write, name1: &str, name2: &str) -> io::Result<i32> {
+    let mut elements = Vec::new();
+    for name in [name1, name2] {
+        match gst::ElementFactory::make(name).build() {
+            Ok(element) => elements.push(element),
+            Err(_) => {
+                writeln!(w, "Could not create element '{}'", name)?;
+                return Ok(-1);
+            }
+        }
+    }
+    let (upstream, downstream) = (&elements[0], &elements[1]);
+    let pipeline = gst::Pipeline::new(None);
+    if pipeline.add_many(&[upstream, downstream]).is_err() {
+        writeln!(w, "Could not add elements to a pipeline")?;
+        return Ok(-1);
+    }
+
+    let mut timings = Vec::new();
+    for _ in 0..CAPS_NEGOTIATION_RUNS {
+        let start = std::time::Instant::now();
+        let linked = upstream.link(downstream);
+        let elapsed = start.elapsed();
+        if linked.is_err() {
+            writeln!(w, "Could not link '{}' to '{}'", name1, name2)?;
+            return Ok(1);
+        }
+        upstream.unlink(downstream);
+        timings.push(elapsed.as_secs_f64() * 1_000_000.0);
+    }
+
+    let min = timings.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = timings.iter().copied().fold(0.0, f64::max);
+    let mean = timings.iter().sum::<f64>() / timings.len() as f64;
+    print_property(w, "Runs", &CAPS_NEGOTIATION_RUNS.to_string(), 6, 0, true)?;
+    print_property(w, "Min", &format!("{:.1} µs", min), 6, 0, true)?;
+    print_property(w, "Max", &format!("{:.1} µs", max), 6, 0, true)?;
+    print_property(w, "Mean", &format!("{:.1} µs", mean), 6, 0, true)?;
+
+    Ok(0)
+}
+
+// What --diff compares, each entry rendered as a single line so that any
+// change shows up as a removed and an added line.
+struct ElementInfoSnapshot {
+    properties: Vec<String>,
+    pad_templates: Vec<String>,
+    signals: Vec<String>,
+}
+
+impl ElementInfoSnapshot {
+    fn new(factory: &gst::ElementFactory, element: &gst::Element) -> Self {
+        let mut properties: Vec<String> = element
+            .list_properties()
+            .iter()
+            .map(|pspec| format!("{}: {}", pspec.name(), describe_pspec(pspec)))
+            .collect();
+        properties.sort();
+
+        let mut pad_templates: Vec<String> = factory
+            .static_pad_templates()
+            .iter()
+            .map(|pad_tmpl| {
+                format!(
+                    "{} ({:?}, {:?}): {}",
+                    pad_tmpl.name_template(),
+                    pad_tmpl.direction(),
+                    pad_tmpl.presence(),
+                    pad_tmpl.caps()
+                )
+            })
+            .collect();
+        pad_templates.sort();
+
+        let mut signals = Vec::new();
+        let mut type_ = Some(element.type_());
+        while let Some(t) = type_ {
+            for signal_id in signal_ids(t) {
+                let query = signal_id.query();
+                let params: Vec<String> = query
+                    .param_types()
+                    .iter()
+                    .map(|param_type| signal_arg_type_name(param_type.type_()))
+                    .collect();
+                signals.push(format!(
+                    "{} ({}) -> {}",
+                    query.signal_name(),
+                    params.join(", "),
+                    signal_arg_type_name(query.return_type().type_())
+                ));
+            }
+            type_ = t.parent();
+        }
+        signals.sort();
+
+        ElementInfoSnapshot {
+            properties,
+            pad_templates,
+            signals,
+        }
+    }
+}
+
+fn print_snapshot_diff(w: &mut dyn Write, old: &[String], new: &[String]) -> io::Result<()> {
+    let mut lines: Vec<&String> = old.iter().chain(new.iter()).collect();
+    lines.sort();
+    lines.dedup();
+
+    for line in lines {
+        match (old.contains(line), new.contains(line)) {
+            (true, false) => writeln!(w, "{}", REMOVED_COLOR.paint(format!("- {}", line)))?,
+            (false, true) => writeln!(w, "{}", ADDED_COLOR.paint(format!("+ {}", line)))?,
+            _ => writeln!(w, "  {}", line)?,
+        }
+    }
+
+    Ok(())
+}
+
+fn print_element_diff(w: &mut dyn Write, name1: &str, name2: &str) -> io::Result<i32> {
+    let mut snapshots = Vec::new();
+    for name in [name1, name2] {
+        let factory = match gst::ElementFactory::find(name) {
+            Some(factory) => factory,
+            None => {
+                writeln!(w, "No such element '{}'", name)?;
+                return Ok(-1);
+            }
+        };
+        let element = match factory.create_with_name(None) {
+            Ok(element) => element,
+            Err(_) => {
+                writeln!(w, "Could not create element '{}'", name)?;
+                return Ok(-1);
+            }
+        };
+        snapshots.push(ElementInfoSnapshot::new(&factory, &element));
+    }
+    let (old, new) = (&snapshots[0], &snapshots[1]);
+
+    writeln!(w, "{}", REMOVED_COLOR.paint(format!("--- {}", name1)))?;
+    writeln!(w, "{}", ADDED_COLOR.paint(format!("+++ {}", name2)))?;
+    for (title, old_lines, new_lines) in [
+        ("Element Properties", &old.properties, &new.properties),
+        ("Pad Templates", &old.pad_templates, &new.pad_templates),
+        ("Element Signals", &old.signals, &new.signals),
+    ] {
+        writeln!(w)?;
+        writeln!(w, "{}:", HEADING_COLOR.paint(title))?;
+        print_snapshot_diff(w, old_lines, new_lines)?;
+    }
+
+    Ok(0)
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn dot_record_escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        if "{}|<> \"\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn print_element_dot(
+    w: &mut dyn Write,
+    element: &gst::Element,
+    factory: &gst::ElementFactory,
+) -> io::Result<()> {
+    let name = factory.name();
+    let mut pad_templates = factory.static_pad_templates();
+    pad_templates.sort_by(|t1, t2| t1.name_template().cmp(t2.name_template()));
+
+    let ports = |direction: gst::PadDirection| {
+        pad_templates
+            .iter()
+            .enumerate()
+            .filter(|(_, pad_tmpl)| pad_tmpl.direction() == direction)
+            .map(|(i, pad_tmpl)| {
+                format!("<p{}> {}", i, dot_record_escape(pad_tmpl.name_template()))
+            })
+            .collect::<Vec<String>>()
+            .join("|")
+    };
+
+    writeln!(w, "digraph \"{}\" {{", dot_escape(&name))?;
+    writeln!(w, "  rankdir=LR;")?;
+    writeln!(w, "  node [fontname=\"monospace\", fontsize=10];")?;
+    writeln!(
+        w,
+        "  \"{}\" [shape=record, label=\"{{ {{{}}} | {}\\n{} | {{{}}} }}\"];",
+        dot_escape(&name),
+        ports(gst::PadDirection::Sink),
+        dot_record_escape(&name),
+        dot_record_escape(element.type_().name()),
+        ports(gst::PadDirection::Src)
+    )?;
+
+    for (i, pad_tmpl) in pad_templates.iter().enumerate() {
+        let caps = pad_tmpl.caps().to_string().replace("; ", ";\n");
+        let caps_node = format!("{}_caps_p{}", name, i);
+        let caps_label = caps
+            .lines()
+            .map(|line| format!("{}\\l", dot_escape(line)))
+            .collect::<String>();
+        writeln!(
+            w,
+            "  \"{}\" [shape=note, label=\"{}\"];",
+            dot_escape(&caps_node),
+            caps_label
+        )?;
+        match pad_tmpl.direction() {
+            gst::PadDirection::Src => writeln!(
+                w,
+                "  \"{}\":p{} -> \"{}\" [style=dashed, arrowhead=none];",
+                dot_escape(&name),
+                i,
+                dot_escape(&caps_node)
+            )?,
+            _ => writeln!(
+                w,
+                "  \"{}\" -> \"{}\":p{} [style=dashed, arrowhead=none];",
+                dot_escape(&caps_node),
+                dot_escape(&name),
+                i
+            )?,
+        }
+    }
+    writeln!(w, "}}")?;
+
+    Ok(())
+}
+
+fn print_feature_dot(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+    match factory.create_with_name(None) {
+        Ok(element) => {
+            print_element_dot(w, &element, &factory)?;
+            Ok(0)
+        }
+        Err(_) => {
+            writeln!(w, "Could not create element '{}'", element_name)?;
+            Ok(-1)
+        }
+    }
+}
+
+fn print_caps_table(w: &mut dyn Write, element_name: &str) -> io::Result<i32> {
+    let factory = match gst::ElementFactory::find(element_name) {
+        Some(factory) => factory,
+        None => {
+            writeln!(w, "No such element '{}'", element_name)?;
+            return Ok(-1);
+        }
+    };
+
+    let field = |structure: &gst::StructureRef, name: &str| {
+        structure
+            .value(name)
+            .ok()
+            .and_then(|value| format_field_value(value, 0))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let mut rows = Vec::new();
+    for pad_tmpl in factory.static_pad_templates() {
+        let caps = pad_tmpl.caps();
+        for structure in caps.iter() {
+            if !["video/x-raw", "video/x-h264"].contains(&structure.name().as_str()) {
+                continue;
+            }
+            let format_field = if structure.has_field("format") {
+                "format"
+            } else {
+                "stream-format"
+            };
+            // One row per format so that the table reads as a matrix.
+            let formats = match structure.get::<gst::List>(format_field) {
+                Ok(list) => list
+                    .as_slice()
+                    .iter()
+                    .filter_map(|value| value.serialize().ok())
+                    .map(|value| value.to_string())
+                    .collect(),
+                Err(_) => vec![field(structure, format_field)],
+            };
+            for format in formats {
+                rows.push([
+                    pad_tmpl.name_template().to_string(),
+                    structure.name().to_string(),
+                    format,
+                    field(structure, "width"),
+                    field(structure, "height"),
+                ]);
+            }
+        }
+    }
+
+    if rows.is_empty() {
+        writeln!(w, "No video/x-raw or video/x-h264 pad template caps")?;
+        return Ok(1);
+    }
+
+    let header = ["Pad", "Media type", "Format", "Width", "Height"];
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].len())
+                .chain([header[i].len()])
+                .max()
+                .unwrap()
+        })
+        .collect();
+    let format_row = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
+
+    let header: Vec<String> = header.iter().map(|cell| cell.to_string()).collect();
+    writeln!(w, "{}", HEADING_COLOR.paint(format_row(&header)))?;
+    writeln!(
+        w,
+        "{}",
+        "-".repeat(widths.iter().sum::<usize>() + 3 * (widths.len() - 1))
+    )?;
+    for row in &rows {
+        writeln!(w, "{}", format_row(row))?;
+    }
+
+    Ok(0)
+}
+
+fn print_caps_path(
+    w: &mut dyn Write,
+    src_name: &str,
+    sink_name: &str,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    let mut elements = Vec::new();
+    for name in [src_name, sink_name] {
+        match gst::ElementFactory::make(name).build() {
+            Ok(element) => elements.push(element),
+            Err(_) => {
+                writeln!(w, "Could not create element '{}'", name)?;
+                return Ok(-1);
+            }
+        }
+    }
+    let (src, sink) = (&elements[0], &elements[1]);
+
+    if src.link(sink).is_err() {
+        writeln!(w, "Could not link '{}' to '{}'", src_name, sink_name)?;
+        return Ok(-1);
+    }
+
+    // Linking may have requested new pads, so look for the pair that actually
+    // got connected instead of assuming "src" and "sink".
+    let (src_pad, sink_pad) = match src
+        .src_pads()
+        .into_iter()
+        .find_map(|pad| pad.peer().map(|peer| (pad, peer)))
+    {
+        Some(pads) => pads,
+        None => {
+            writeln!(w, "Could not find the linked pads")?;
+            return Ok(-1);
+        }
+    };
+
+    let pad_label = |element: &str, pad: &gst::Pad| format!("{}:{}", element, pad.name());
+
+    writeln!(
+        w,
+        "{} → {}",
+        ELEMENT_NAME_COLOR.paint(&pad_label(src_name, &src_pad)),
+        ELEMENT_NAME_COLOR.paint(&pad_label(sink_name, &sink_pad))
+    )?;
+
+    let proposed = src_pad.query_caps(None);
+    writeln!(w)?;
+    writeln!(
+        w,
+        "{} {}",
+        HEADING_COLOR.paint("propose:"),
+        pad_label(src_name, &src_pad)
+    )?;
+    print_caps(w, &proposed, options.annotate_fractions)?;
+
+    let filtered = sink_pad.query_caps(Some(&proposed));
+    writeln!(w)?;
+    writeln!(
+        w,
+        "→ {} {}",
+        HEADING_COLOR.paint("filter:"),
+        pad_label(sink_name, &sink_pad)
+    )?;
+    print_caps(w, &filtered, options.annotate_fractions)?;
+
+    let accepted = !filtered.is_empty() && sink_pad.query_accept_caps(&filtered);
+    writeln!(w)?;
+    writeln!(
+        w,
+        "→ {} {}",
+        HEADING_COLOR.paint("accept:"),
+        PROP_ATTR_VALUE_COLOR.paint(if accepted { "yes" } else { "no" })
+    )?;
+
+    writeln!(w)?;
+    writeln!(w, "{}", HEADING_COLOR.paint("Resulting intersection:"))?;
+    print_caps(
+        w,
+        &proposed.intersect(&filtered),
+        options.annotate_fractions,
+    )?;
+
+    Ok(if accepted { 0 } else { 1 })
+}
+
+fn print_all_elements(
+    w: &mut dyn Write,
+    fmt: &dyn Formatter,
+    jobs: usize,
+    options: &InspectOptions,
+) -> io::Result<i32> {
+    let registry = gst::Registry::get();
+    let mut features: Vec<gst::PluginFeature> = registry
+        .features(gst::ElementFactory::static_type())
+        .into_iter()
+        .collect();
+    features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+
+    let total = features.len();
+    let done = AtomicUsize::new(0);
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool,
+        Err(err) => {
+            eprintln!("Could not start {} jobs: {}", jobs, err);
+            return Ok(-1);
+        }
+    };
+
+    // Every element is rendered into its own buffer so that the jobs can run
+    // in parallel while the output keeps the registry order.
+    let outputs: Vec<io::Result<Vec<u8>>> = pool.install(|| {
+        features
+            .par_iter()
+            .map(|feature| {
+                let mut buf = Vec::new();
+                print_element_info(&mut buf, fmt, feature, options)?;
+                let i = done.fetch_add(1, Ordering::SeqCst) + 1;
+                eprintln!("[{}/{}] {}", i, total, feature.name());
+                Ok(buf)
+            })
+            .collect()
+    });
+
+    for output in outputs {
+        w.write_all(&output?)?;
+        fmt.document_separator(w)?;
+    }
+
+    Ok(0)
+}
+
+fn write_element_to_dir(
+    dir: &std::path::Path,
+    plugin: &str,
+    element: &str,
+    contents: &[u8],
+) -> io::Result<()> {
+    let plugin_dir = dir.join(plugin);
+    std::fs::create_dir_all(&plugin_dir)?;
+    std::fs::write(plugin_dir.join(format!("{}.json", element)), contents)
+}
+
+// One file per element lets a documentation site be built by walking the
+// directory instead of splitting a single huge output. The files hold the
+// same records as --format json prints for each element, progress and
+// failures are only status and go to stderr.
+fn write_all_elements_to_dir(dir: &str, jobs: usize, options: &InspectOptions) -> io::Result<i32> {
+    let registry = gst::Registry::get();
+    let mut features: Vec<gst::PluginFeature> = registry
+        .features(gst::ElementFactory::static_type())
+        .into_iter()
+        .collect();
+    features.sort_by(|f1, f2| f1.name().as_str().cmp(f2.name().as_str()));
+
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool,
+        Err(err) => {
+            eprintln!("Could not start {} jobs: {}", jobs, err);
+            return Ok(-1);
+        }
+    };
+
+    let results: Vec<io::Result<Option<String>>> = pool.install(|| {
+        features
+            .par_iter()
+            .map(|feature| {
+                let plugin_name = match feature.plugin_name() {
+                    Some(plugin_name) => plugin_name,
+                    None => {
+                        return Ok(Some(format!(
+                            "Skipping '{}', it does not belong to a plugin",
+                            feature.name()
+                        )))
+                    }
+                };
+                let mut buf = Vec::new();
+                if print_element_info(&mut buf, &JsonFormatter, feature, options)? != 0 {
+                    return Ok(Some(format!(
+                        "Could not inspect element '{}'",
+                        feature.name()
+                    )));
+                }
+                write_element_to_dir(
+                    std::path::Path::new(dir),
+                    plugin_name.as_str(),
+                    feature.name().as_str(),
+                    &buf,
+                )?;
+                Ok(None)
+            })
+            .collect()
+    });
+
+    let mut n_failed = 0;
+    for result in results {
+        if let Some(message) = result? {
+            eprintln!("{}", message);
+            n_failed += 1;
+        }
+    }
+    eprintln!("Wrote {} elements to {}", features.len() - n_failed, dir);
+
+    Ok(if n_failed == 0 { 0 } else { 1 })
+}
+
+fn print_caps_from_fields(
+    w: &mut dyn Write,
+    media_type: &str,
+    fields: &[&String],
+) -> io::Result<i32> {
+    let mut caps_str = media_type.to_string();
+    for field in fields {
+        if !field.contains('=') {
+            writeln!(w, "Invalid caps field '{}', expected FIELD=VALUE", field)?;
+            return Ok(-1);
+        }
+        caps_str.push_str(", ");
+        caps_str.push_str(field);
+    }
+
+    match caps_str.parse::<gst::Caps>() {
+        Ok(caps) => {
+            writeln!(w, "{}", caps)?;
+            Ok(0)
+        }
+        Err(_) => {
+            writeln!(w, "Could not build caps from '{}'", caps_str)?;
+            Ok(-1)
+        }
+    }
+}
+
+const BIN_NAME: &str = "gst-inspect-rs";
+const COMPLETION_SHELLS: [&str; 4] = ["bash", "zsh", "fish", "elvish"];
+
+const BASH_DYNAMIC_COMPLETION: &str = r#"_gst_inspect_rs() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    if [[ "$cur" == -* ]]; then
+        return
+    fi
+    COMPREPLY=($(compgen -W "$(gst-inspect-rs --print-feature-names 2>/dev/null)" -- "$cur"))
+}
+complete -F _gst_inspect_rs gst-inspect-rs
+"#;
+
+const ZSH_DYNAMIC_COMPLETION: &str = r#"#compdef gst-inspect-rs
+
+_gst_inspect_rs() {
+    local -a names
+    names=(${(f)"$(gst-inspect-rs --print-feature-names 2>/dev/null)"})
+    _describe 'element or plugin' names
+}
+
+compdef _gst_inspect_rs gst-inspect-rs
+"#;
+
+const FISH_DYNAMIC_COMPLETION: &str = r#"complete -c gst-inspect-rs -f -a '(gst-inspect-rs --print-feature-names 2>/dev/null)'
+"#;
+
+const ELVISH_DYNAMIC_COMPLETION: &str = r#"set edit:completion:arg-completer[gst-inspect-rs] = {|@words|
+    e:gst-inspect-rs --print-feature-names 2>/dev/null
+}
+"#;
+
+fn feature_and_plugin_names() -> Vec<String> {
+    let registry = gst::Registry::get();
+    let mut names: Vec<String> = registry
+        .features(gst::ElementFactory::static_type())
+        .iter()
+        .map(|feature| feature.name().to_string())
+        .chain(
+            registry
+                .plugins()
+                .iter()
+                .map(|plugin| plugin.plugin_name().to_string()),
+        )
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn print_feature_names(w: &mut dyn Write) -> io::Result<()> {
+    for name in feature_and_plugin_names() {
+        writeln!(w, "{}", name)?;
+    }
+
+    Ok(())
+}
+
+fn print_completions(w: &mut dyn Write, shell: &str) -> io::Result<i32> {
+    let shell = match shell.parse::<Shell>() {
+        Ok(shell) => shell,
+        Err(_) => {
+            writeln!(w, "Unsupported shell '{}'", shell)?;
+            return Ok(-1);
+        }
+    };
+    let mut command = build_command().mut_arg("ELEMENT-NAME | PLUGIN-NAME", |arg| {
+        arg.value_parser(PossibleValuesParser::new(feature_and_plugin_names()))
+    });
+
+    clap_complete::generate(shell, &mut command, BIN_NAME, w);
+
+    Ok(0)
+}
+
+fn print_dynamic_completions(w: &mut dyn Write, shell: &str) -> io::Result<i32> {
+    let script = match shell {
+        "bash" => BASH_DYNAMIC_COMPLETION,
+        "zsh" => ZSH_DYNAMIC_COMPLETION,
+        "fish" => FISH_DYNAMIC_COMPLETION,
+        "elvish" => ELVISH_DYNAMIC_COMPLETION,
+        _ => {
+            writeln!(w, "Unsupported shell '{}'", shell)?;
+            return Ok(-1);
+        }
+    };
+    write!(w, "{}", script)?;
+
+    Ok(0)
+}
+
+fn build_command() -> Command {
+    Command::new("prog")
+        .arg(Arg::new("ELEMENT-NAME | PLUGIN-NAME"))
+        .arg(
+            Arg::new("caps-to-string")
+                .long("caps-to-string")
+                .num_args(1..)
+                .value_names(["MIME-TYPE", "FIELD=VALUE"])
+                .help("Build caps from a media type and fields and print them serialized"),
+        )
+        .arg(
+            Arg::new("group-by")
+                .long("group-by")
+                .value_name("KEY")
+                .value_parser(["plugin", "klass"])
+                .default_value("plugin")
+                .help("Group the element list by plugin or by element klass"),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .action(ArgAction::SetTrue)
+                .help("List elements as uncolored 'plugin/element - description' lines"),
+        )
+        .arg(
+            Arg::new("min-rank")
+                .long("min-rank")
+                .value_name("RANK")
+                .value_parser(rank_from_str)
+                .default_value("none")
+                .help("Only list features with at least the given rank (none, marginal, secondary, primary or N)"),
+        )
+        .arg(
+            Arg::new("sort-by")
+                .long("sort-by")
+                .value_name("KEY")
+                .value_parser(clap::value_parser!(SortKey))
+                .default_value("plugin")
+                .help("Sort the element list by name, rank, plugin or klass"),
+        )
+        .arg(
+            Arg::new("list-elements-with-property")
+                .long("list-elements-with-property")
+                .value_name("PROPERTY-NAME")
+                .help("List the elements that have a property with the given name"),
+        )
+        .arg(
+            Arg::new("list-elements-with-preset")
+                .long("list-elements-with-preset")
+                .action(ArgAction::SetTrue)
+                .help("List all elements that provide presets, sorted by preset count"),
+        )
+        .arg(
+            Arg::new("list-elements-by-version")
+                .long("list-elements-by-version")
+                .value_name("GSTREAMER-VERSION")
+                .help("List only the elements available in the given GStreamer version"),
+        )
+        .arg(
+            Arg::new("list-elements-by-filename")
+                .long("list-elements-by-filename")
+                .value_name("GLOB-PATTERN")
+                .help("List only the elements of plugins whose file path matches the pattern"),
+        )
+        .arg(
+            Arg::new("element-provides-tags")
+                .long("element-provides-tags")
+                .action(ArgAction::SetTrue)
+                .help("List the demuxers and parsers that extract tags, sorted by media type"),
+        )
+        .arg(
+            Arg::new("list-elements-no-pads")
+                .long("list-elements-no-pads")
+                .action(ArgAction::SetTrue)
+                .help("List elements without any pad templates"),
+        )
+        .arg(
+            Arg::new("all")
+                .long("all")
+                .short('a')
+                .action(ArgAction::SetTrue)
+                .help("Print detailed information for all elements"),
+        )
+        .arg(
+            Arg::new("jobs")
+                .long("jobs")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("1")
+                .help("Number of elements to inspect in parallel with --all"),
+        )
+        .arg(
+            Arg::new("generate-completions")
+                .long("generate-completions")
+                .value_name("SHELL")
+                .value_parser(COMPLETION_SHELLS)
+                .help("Generate a shell completion script with the currently installed element and plugin names"),
+        )
+        .arg(
+            Arg::new("generate-completions-dynamic")
+                .long("generate-completions-dynamic")
+                .value_name("SHELL")
+                .value_parser(COMPLETION_SHELLS)
+                .help("Generate a shell completion script that queries the registry at completion time"),
+        )
+        .arg(
+            Arg::new("test-events")
+                .long("test-events")
+                .action(ArgAction::SetTrue)
+                .help("Test which events the element accepts in READY state"),
+        )
+        .arg(
+            Arg::new("section")
+                .long("section")
+                .value_name("SECTION")
+                .value_parser(clap::value_parser!(Section))
+                .action(ArgAction::Append)
+                .help("Only print the given section of the element details, can be repeated"),
+        )
+        .arg(
+            Arg::new("show-type-ids")
+                .long("show-type-ids")
+                .action(ArgAction::SetTrue)
+                .help("Show the numeric GType ID of each type in the hierarchy"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .long("verbose")
+                .short('v')
+                .action(ArgAction::SetTrue)
+                .help("Expand structured property values field by field"),
+        )
+        .arg(
+            Arg::new("annotate-fractions")
+                .long("annotate-fractions")
+                .action(ArgAction::SetTrue)
+                .help("Annotate fractions in caps with their frame rate or tick length"),
+        )
+        .arg(
+            Arg::new("show-tracer-hooks")
+                .long("show-tracer-hooks")
+                .action(ArgAction::SetTrue)
+                .help("List the hooks a tracer registers when inspecting a tracer"),
+        )
+        .arg(
+            Arg::new("show-meta-types")
+                .long("show-meta-types")
+                .action(ArgAction::SetTrue)
+                .help("List the buffer meta types the element typically attaches"),
+        )
+        .arg(
+            Arg::new("show-owner")
+                .long("show-owner")
+                .action(ArgAction::SetTrue)
+                .help("Show which base class a property is inherited from"),
+        )
+        .arg(
+            Arg::new("show-latency")
+                .long("show-latency")
+                .action(ArgAction::SetTrue)
+                .help("Query the element's latency in a PAUSED fakesrc ! element ! fakesink pipeline"),
+        )
+        .arg(
+            Arg::new("show-control-binding-examples")
+                .long("show-control-binding-examples")
+                .action(ArgAction::SetTrue)
+                .help("Show how to bind a control source to the controllable properties"),
+        )
+        .arg(
+            Arg::new("element-api-version")
+                .long("element-api-version")
+                .value_name("ELEMENT")
+                .help("Check whether the element's plugin loads with the runtime GStreamer API"),
+        )
+        .arg(
+            Arg::new("debug-caps-path")
+                .long("debug-caps-path")
+                .num_args(2)
+                .value_names(["ELEMENT1", "ELEMENT2"])
+                .help("Link two elements and trace how caps are negotiated between them"),
+        )
+        .arg(
+            Arg::new("element-performance-category")
+                .long("element-performance-category")
+                .value_name("ELEMENT")
+                .help("Classify the expected performance impact of an element"),
+        )
+        .arg(
+            Arg::new("show-install-command")
+                .long("show-install-command")
+                .value_name("ELEMENT")
+                .help("Suggest a package manager command that installs a missing element"),
+        )
+        .arg(
+            Arg::new("validate-pipeline-string")
+                .long("validate-pipeline-string")
+                .value_name("PIPELINE")
+                .help("Check that a pipeline description parses, without running it"),
+        )
+        .arg(
+            Arg::new("inspect-pipeline")
+                .long("inspect-pipeline")
+                .value_name("PIPELINE")
+                .help("Preroll a pipeline description and summarize the pads, caps and set properties of its elements"),
+        )
+        .arg(
+            Arg::new("element-topology-graph")
+                .long("element-topology-graph")
+                .value_name("FILE")
+                .requires("ELEMENT-NAME | PLUGIN-NAME")
+                .help("Write the internal structure of a bin element as a DOT graph to FILE"),
+        )
+        .arg(
+            Arg::new("element-pad-count-check")
+                .long("element-pad-count-check")
+                .num_args(3)
+                .value_names(["ELEMENT", "SRC-COUNT", "SINK-COUNT"])
+                .help("Check the number of always-present src and sink pad templates of an element"),
+        )
+        .arg(
+            Arg::new("property-default-changed")
+                .long("property-default-changed")
+                .value_name("ELEMENT")
+                .help("Warn about properties whose value at creation differs from their declared default"),
+        )
+        .arg(
+            Arg::new("property-watcher")
+                .long("property-watcher")
+                .value_names(["ELEMENT", "PROPERTY", "INTERVAL-MS"])
+                .num_args(3)
+                .help("Run an element in a test pipeline and print every change of a property, polled every INTERVAL-MS milliseconds"),
+        )
+        .arg(
+            Arg::new("check-caps-strict")
+                .long("check-caps-strict")
+                .value_name("ELEMENT")
+                .help("List the pad template caps fields of an element that use lists, ranges or ANY"),
+        )
+        .arg(
+            Arg::new("show-toc")
+                .long("show-toc")
+                .value_name("FILE")
+                .requires("ELEMENT-NAME | PLUGIN-NAME")
+                .help("Run the given element on FILE and print the table of contents it finds"),
+        )
+        .arg(
+            Arg::new("element-is-reentrant")
+                .long("element-is-reentrant")
+                .value_name("ELEMENT")
+                .help("Show known thread-safety characteristics of an element"),
+        )
+        .arg(
+            Arg::new("element-memory-model")
+                .long("element-memory-model")
+                .value_name("ELEMENT")
+                .help("Guess whether an element can operate without copying buffers"),
+        )
+        .arg(
+            Arg::new("show-pipeline-integration")
+                .long("show-pipeline-integration")
+                .value_name("ELEMENT")
+                .help("Show how an element is typically used in a pipeline, with a gst-launch-1.0 example"),
+        )
+        .arg(
+            Arg::new("watch-registry")
+                .long("watch-registry")
+                .action(ArgAction::SetTrue)
+                .help("Rescan the registry periodically and report added, removed or changed plugins"),
+        )
+        .arg(
+            Arg::new("watch-interval")
+                .long("watch-interval")
+                .value_name("SECONDS")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("2")
+                .help("Seconds between two registry scans of --watch-registry"),
+        )
+        .arg(
+            Arg::new("element-export-gir")
+                .long("element-export-gir")
+                .value_name("ELEMENT")
+                .help("Print a minimal GObject introspection (GIR) fragment for an element's type, properties and signals"),
+        )
+        .arg(
+            Arg::new("list-tracers")
+                .long("list-tracers")
+                .action(ArgAction::SetTrue)
+                .help("List all registered tracers"),
+        )
+        .arg(
+            Arg::new("list-device-providers")
+                .long("list-device-providers")
+                .action(ArgAction::SetTrue)
+                .help("List all registered device providers"),
+        )
+        .arg(
+            Arg::new("tracer")
+                .long("tracer")
+                .value_name("TRACER")
+                .help("Print information about a tracer"),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["text", "markdown", "json", "sql", "avro", "dot"])
+                .default_value("text")
+                .help("Output format; markdown documents the given element, json writes the same sections as one JSON object per line, sql dumps the registry as PostgreSQL INSERT statements, avro as an Avro object container file, dot draws the given element's pads as a Graphviz graph"),
+        )
+        .arg(
+            Arg::new("property-type-count")
+                .long("property-type-count")
+                .value_name("ELEMENT")
+                .num_args(0..=1)
+                .help("Count the properties of an element by type, or of all elements with --all"),
+        )
+        .arg(
+            Arg::new("element-has-interface")
+                .long("element-has-interface")
+                .num_args(2)
+                .value_names(["ELEMENT", "INTERFACE"])
+                .help("Check whether an element implements an interface (exit 0: yes, 1: no, 2: no such element, 3: unknown interface)"),
+        )
+        .arg(
+            Arg::new("show-plugin-path")
+                .long("show-plugin-path")
+                .action(ArgAction::SetTrue)
+                .help("Show the plugin search path and the directories plugins were loaded from"),
+        )
+        .arg(
+            Arg::new("gst-plugin-path")
+                .long("gst-plugin-path")
+                .value_name("DIR")
+                .help("Prepend DIR to GST_PLUGIN_PATH before initializing GStreamer"),
+        )
+        .arg(
+            Arg::new("print-all-signals")
+                .long("print-all-signals")
+                .value_name("SIGNAL-NAME")
+                .help("List all elements that have a signal with the given name"),
+        )
+        .arg(
+            Arg::new("check-all")
+                .long("check-all")
+                .action(ArgAction::SetTrue)
+                .help("Check that every plugin in the registry can be loaded"),
+        )
+        .arg(
+            Arg::new("compare-elements")
+                .long("compare-elements")
+                .num_args(2)
+                .value_names(["ELEMENT1", "ELEMENT2"])
+                .help("Compare the properties of two elements side by side"),
+        )
+        .arg(
+            Arg::new("measure-caps-negotiation-time")
+                .long("measure-caps-negotiation-time")
+                .num_args(2)
+                .value_names(["ELEMENT1", "ELEMENT2"])
+                .help("Link two elements repeatedly and report how long caps negotiation takes"),
+        )
+        .arg(
+            Arg::new("diff")
+                .long("diff")
+                .num_args(2)
+                .value_names(["ELEMENT1", "ELEMENT2"])
+                .help("Show the properties, pad templates and signals that differ between two elements"),
+        )
+        .arg(
+            Arg::new("show-caps-as-table")
+                .long("show-caps-as-table")
+                .value_name("ELEMENT")
+                .help("Show the video formats and sizes of an element's pad templates as a table"),
+        )
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .short('o')
+                .value_name("FILE")
+                .help("Write the output to FILE instead of stdout"),
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .value_name("DIR")
+                .requires("all")
+                .help("With --all --format json, write each element to DIR/<plugin>/<element>.json"),
+        )
+        .arg(
+            Arg::new("all-features")
+                .long("all-features")
+                .action(ArgAction::SetTrue)
+                .help("Break the total at the end of the element list down by feature type"),
+        )
+        .arg(
+            Arg::new("print-plugin-auto-install-info")
+                .long("print-plugin-auto-install-info")
+                .action(ArgAction::SetTrue)
+                .help("Print what every element provides in the missing plugin installer format"),
+        )
+        .arg(
+            Arg::new("print-feature-names")
+                .long("print-feature-names")
+                .action(ArgAction::SetTrue)
+                .hide(true),
+        )
+}
+
+fn run(w: &mut dyn Write, matches: &ArgMatches) -> io::Result<i32> {
+    let mut st: i32 = 0;
+    let mut cache = ElementCache::default();
+    let options = InspectOptions {
+        sections: matches
+            .get_many::<Section>("section")
+            .map_or(HashSet::new(), |sections| sections.copied().collect()),
+        test_events: matches.get_flag("test-events"),
+        show_type_ids: matches.get_flag("show-type-ids"),
+        verbose: matches.get_flag("verbose"),
+        annotate_fractions: matches.get_flag("annotate-fractions"),
+        show_tracer_hooks: matches.get_flag("show-tracer-hooks"),
+        show_meta_types: matches.get_flag("show-meta-types"),
+        show_owner: matches.get_flag("show-owner"),
+        show_latency: matches.get_flag("show-latency"),
+        show_control_bindings: matches.get_flag("show-control-binding-examples"),
+    };
+
+    let format = matches.get_one::<String>("format").unwrap();
+    let fmt: &dyn Formatter = match format.as_str() {
+        "markdown" => &MarkdownFormatter,
+        "json" => &JsonFormatter,
+        _ if output_uses_color(matches) => &TerminalFormatter,
+        _ => &PlainFormatter,
+    };
+    if format == "sql" {
+        print_registry_sql(w)?;
+    } else if format == "avro" {
+        write_registry_avro(w)?;
+    } else if format == "dot" {
+        match matches.get_one::<String>("ELEMENT-NAME | PLUGIN-NAME") {
+            Some(element_name) => st = print_feature_dot(w, element_name)?,
+            None => {
+                writeln!(w, "--format dot requires an element name")?;
+                st = -1;
+            }
+        }
+    } else if let Some(shell) = matches.get_one::<String>("generate-completions") {
+        st = print_completions(w, shell)?;
+    } else if let Some(shell) = matches.get_one::<String>("generate-completions-dynamic") {
+        st = print_dynamic_completions(w, shell)?;
+    } else if matches.get_flag("print-plugin-auto-install-info") {
+        print_plugin_auto_install_info(w)?;
+    } else if matches.get_flag("print-feature-names") {
+        print_feature_names(w)?;
+    } else if matches.contains_id("property-type-count") {
+        match matches.get_one::<String>("property-type-count") {
+            Some(element_name) if !matches.get_flag("all") => {
+                st = print_property_type_count(w, element_name)?;
+            }
+            _ => print_all_property_type_counts(w)?,
+        }
+    } else if let Some(dir) = matches.get_one::<String>("output-dir") {
+        if format == "json" {
+            st = write_all_elements_to_dir(
+                dir,
+                *matches.get_one::<usize>("jobs").unwrap(),
+                &options,
+            )?;
+        } else {
+            writeln!(w, "--output-dir requires --format json")?;
+            st = -1;
+        }
+    } else if matches.get_flag("all") {
+        st = print_all_elements(w, fmt, *matches.get_one::<usize>("jobs").unwrap(), &options)?;
+    } else if matches.get_flag("list-tracers") {
+        print_tracer_list(w)?;
+    } else if matches.get_flag("list-device-providers") {
+        print_device_provider_list(w)?;
+    } else if let Some(tracer_name) = matches.get_one::<String>("tracer") {
+        st = print_tracer_by_name(w, fmt, tracer_name, &options)?;
+    } else if matches.get_flag("list-elements-no-pads") {
+        print_elements_without_pads_list(w)?;
+    } else if let Some(property_name) = matches.get_one::<String>("list-elements-with-property") {
+        print_elements_with_property_list(w, &mut cache, property_name)?;
+    } else if matches.get_flag("list-elements-with-preset") {
+        print_elements_with_preset_list(w, &mut cache)?;
+    } else if matches.get_flag("element-provides-tags") {
+        print_elements_providing_tags_list(w)?;
+    } else if let Some(pattern) = matches.get_one::<String>("list-elements-by-filename") {
+        st = print_element_list_by_filename(w, pattern)?;
+    } else if let Some(version) = matches.get_one::<String>("list-elements-by-version") {
+        st = print_element_list_by_version(w, version)?;
+    } else if let Some(values) = matches.get_many::<String>("caps-to-string") {
+        let values: Vec<&String> = values.collect();
+        st = print_caps_from_fields(w, values[0], &values[1..])?;
+    } else if let Some(element_name) = matches.get_one::<String>("element-api-version") {
+        st = print_element_api_version(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("show-caps-as-table") {
+        st = print_caps_table(w, element_name)?;
+    } else if let Some(values) = matches.get_many::<String>("compare-elements") {
+        let values: Vec<&String> = values.collect();
+        st = print_element_comparison(w, values[0], values[1])?;
+    } else if let Some(values) = matches.get_many::<String>("diff") {
+        let values: Vec<&String> = values.collect();
+        st = print_element_diff(w, values[0], values[1])?;
+    } else if let Some(values) = matches.get_many::<String>("measure-caps-negotiation-time") {
+        let values: Vec<&String> = values.collect();
+        st = measure_caps_negotiation_time(w, values[0], values[1])?;
+    } else if let Some(values) = matches.get_many::<String>("debug-caps-path") {
+        let values: Vec<&String> = values.collect();
+        st = print_caps_path(w, values[0], values[1], &options)?;
+    } else if let Some(element_name) = matches.get_one::<String>("element-performance-category") {
+        st = print_element_performance_category(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("show-pipeline-integration") {
+        st = print_pipeline_integration(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("element-export-gir") {
+        st = print_element_gir(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("element-is-reentrant") {
+        st = print_element_thread_safety(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("element-memory-model") {
+        st = print_element_memory_model(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("show-install-command") {
+        st = print_install_command(w, element_name)?;
+    } else if let Some(pipeline) = matches.get_one::<String>("inspect-pipeline") {
+        st = print_pipeline_inspection(w, pipeline)?;
+    } else if let Some(pipeline) = matches.get_one::<String>("validate-pipeline-string") {
+        st = print_pipeline_validation(w, pipeline)?;
+    } else if let Some(signal_name) = matches.get_one::<String>("print-all-signals") {
+        print_all_signals(w, signal_name)?;
+    } else if matches.get_flag("watch-registry") {
+        st = watch_registry(w, *matches.get_one::<u64>("watch-interval").unwrap())?;
+    } else if matches.get_flag("check-all") {
+        st = check_all_plugins(w)?;
+    } else if matches.get_flag("show-plugin-path") {
+        print_plugin_path(w)?;
+    } else if let Some(values) = matches.get_many::<String>("element-has-interface") {
+        let values: Vec<&String> = values.collect();
+        st = check_element_has_interface(w, values[0], values[1])?;
+    } else if let Some(values) = matches.get_many::<String>("element-pad-count-check") {
+        let values: Vec<&String> = values.collect();
+        st = check_element_pad_count(w, values[0], values[1], values[2])?;
+    } else if let Some(element_name) = matches.get_one::<String>("check-caps-strict") {
+        st = check_caps_strict(w, element_name)?;
+    } else if let Some(element_name) = matches.get_one::<String>("property-default-changed") {
+        st = check_property_defaults(w, element_name)?;
+    } else if let Some(values) = matches.get_many::<String>("property-watcher") {
+        let values: Vec<&String> = values.collect();
+        st = watch_element_property(w, values[0], values[1], values[2])?;
+    } else if let Some(path) = matches.get_one::<String>("show-toc") {
+        let element_name = matches
+            .get_one::<String>("ELEMENT-NAME | PLUGIN-NAME")
+            .unwrap();
+        st = print_toc_from_file(w, element_name, path)?;
+    } else if let Some(path) = matches.get_one::<String>("element-topology-graph") {
+        let element_name = matches
+            .get_one::<String>("ELEMENT-NAME | PLUGIN-NAME")
+            .unwrap();
+        st = write_element_topology_graph(w, element_name, path)?;
+    } else if let Some(fname) = matches.get_one::<String>("ELEMENT-NAME | PLUGIN-NAME") {
+        st = print_feature_info(w, fmt, fname, &options)?;
+    } else if matches.get_one::<String>("group-by").unwrap() == "klass" {
+        print_element_list_by_klass(w)?;
+    } else {
+        print_element_list(
+            w,
+            matches.get_flag("compact"),
+            *matches.get_one::<u32>("min-rank").unwrap(),
+            *matches.get_one::<SortKey>("sort-by").unwrap(),
+            matches.get_flag("all-features"),
+        )?;
+    }
+
+    Ok(st)
+}
+
+// Files and document formats never get colors, stdout only when the
+// terminal can show them.
+fn output_uses_color(matches: &ArgMatches) -> bool {
+    matches.get_one::<String>("output").is_none()
+        && matches.get_one::<String>("format").unwrap() == "text"
+        && stdout_supports_color()
+}
+
+fn main() {
+    let matches = build_command().get_matches();
+
+    let mut output: Box<dyn Write> = match matches.get_one::<String>("output") {
+        Some(path) => match File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(err) => {
+                eprintln!("Could not open '{}' for writing: {}", path, err);
+                std::process::exit(-1);
+            }
+        },
+        None => Box::new(io::stdout()),
+    };
+    // Avro output is binary and must not go through the escape filter.
+    if !output_uses_color(&matches) && matches.get_one::<String>("format").unwrap() != "avro" {
+        output = Box::new(StripEscapes::new(output));
+    }
+
+    if let Some(dir) = matches.get_one::<String>("gst-plugin-path") {
+        let mut paths = vec![std::path::PathBuf::from(dir)];
+        if let Some(plugin_path) = std::env::var_os("GST_PLUGIN_PATH") {
+            paths.extend(std::env::split_paths(&plugin_path));
+        }
+        match std::env::join_paths(paths) {
+            Ok(plugin_path) => std::env::set_var("GST_PLUGIN_PATH", plugin_path),
+            Err(err) => {
+                eprintln!("Invalid plugin path '{}': {}", dir, err);
+                std::process::exit(-1);
+            }
+        }
+    }
+
+    gst::init().unwrap();
+    let st = match run(output.as_mut(), &matches).and_then(|st| output.flush().map(|_| st)) {
+        Ok(st) => st,
+        Err(err) => {
+            eprintln!("Could not write output: {}", err);
+            -1
+        }
+    };
+
+    std::process::exit(st);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipeline_examples_parse() {
+        let examples = PIPELINE_EXAMPLES.parse::<toml::Table>().unwrap();
+
+        for (name, entry) in &examples {
+            let entry = entry.as_table().unwrap();
+            let position = entry.get("position").and_then(|v| v.as_str());
+            assert!(
+                matches!(position, Some("source" | "transform" | "sink")),
+                "{} has no valid position",
+                name
+            );
+            assert!(entry.get("launch").and_then(|v| v.as_str()).is_some());
+        }
+    }
+
+    #[test]
+    fn release_date_day() {
+        assert_eq!(
+            parse_release_date("2023-10-01"),
+            Some("2023-10-01 (2023)".to_string())
+        );
+    }
+
+    #[test]
+    fn release_date_iso_time() {
+        assert_eq!(
+            parse_release_date("2023-10-01T12:30Z"),
+            Some("2023-10-01 (2023)".to_string())
+        );
+    }
+
+    #[test]
+    fn release_date_utc_time() {
+        assert_eq!(
+            parse_release_date("2023-10-01 12:30 (UTC)"),
+            Some("2023-10-01 (2023)".to_string())
+        );
+    }
+
+    #[test]
+    fn release_date_unparseable() {
+        for date in ["October 2023", "2023-13-01", "2023-10-01T25:00Z"] {
+            assert_eq!(parse_release_date(date), None, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn release_date_without_time_zone() {
+        // Not a documented format, the time could be local to anywhere.
+        assert_eq!(parse_release_date("2023-10-01 12:30"), None);
+    }
 }
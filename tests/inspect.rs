@@ -0,0 +1,62 @@
+// These tests run the gst-inspect-rs binary against fakesrc, which is
+// shipped by the core elements plugin and thus always available.
+
+use std::process::Command;
+
+fn inspect(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_gst-inspect-rs"))
+        .args(args)
+        .output()
+        .expect("failed to run gst-inspect-rs");
+    assert!(output.status.success(), "gst-inspect-rs {:?} failed", args);
+
+    String::from_utf8(output.stdout).expect("output is not UTF-8")
+}
+
+#[test]
+fn fakesrc_sections() {
+    let output = inspect(&["fakesrc"]);
+
+    for section in ["Factory details:", "Pad Templates:", "Element Properties:"] {
+        assert!(output.contains(section), "missing section {:?}", section);
+    }
+}
+
+#[test]
+fn fakesrc_properties() {
+    let output = inspect(&["fakesrc"]);
+
+    assert!(output.contains("  name"));
+    for type_name in ["String", "Boolean", "Integer"] {
+        assert!(output.contains(type_name), "missing type {:?}", type_name);
+    }
+}
+
+#[test]
+fn fakesrc_without_colors() {
+    // stdout is a pipe here, so no escape sequence must get through.
+    let output = inspect(&["fakesrc"]);
+
+    assert!(!output.contains('\x1b'));
+}
+
+#[test]
+fn fakesrc_markdown() {
+    let output = inspect(&["--format", "markdown", "fakesrc"]);
+
+    assert!(output.starts_with("# fakesrc\n"));
+    assert!(output.contains("## Factory details"));
+    assert!(output.contains("### name"));
+}
+
+#[test]
+fn pipeline_provided_clock() {
+    // pipeline ships with the core library and provides the system clock.
+    let output = inspect(&["pipeline"]);
+
+    assert!(output.contains("element provides a clock"));
+    for label in ["Type", "Resolution", "Synchronous wait", "Role"] {
+        assert!(output.contains(label), "missing clock {:?}", label);
+    }
+    assert!(output.contains("GstSystemClock"));
+}